@@ -24,7 +24,16 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     match response {
         APIResponse::Success(value) => {
-            println!("{}", value);
+            let results = value.results;
+            println!(
+                "{} of {} results for \"{}\"",
+                results.matches.len(),
+                results.total_results,
+                results.query
+            );
+            for album in results.matches {
+                println!("{} - {}", album.artist, album.name);
+            }
         }
         APIResponse::Error(err) => {
             println!("Error: {} - {}", err.error, err.message);
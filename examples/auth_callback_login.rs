@@ -0,0 +1,27 @@
+use dotenv::dotenv;
+use lastfm_rust::Lastfm;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    let api_key = std::env::var("API_KEY").expect("API_KEY env variable is required");
+    let api_secret = std::env::var("API_SECRET").expect("API_SECRET env variable is required");
+
+    let mut lastfm = Lastfm::builder()
+        .api_key(api_key)
+        .api_secret(api_secret)
+        .build()?;
+
+    // Listen for Last.fm's redirect on any free local port
+    let login = lastfm.auth().callback_login("127.0.0.1:0").await?;
+
+    println!("Please authorize the application by visiting the following URL:");
+    println!("{}", login.authorization_url());
+
+    let session = login.wait_for_session().await?;
+
+    println!("Session key for {}: {}", session.name, session.key);
+    lastfm.set_session(&session);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use dotenv::dotenv;
+use lastfm_rust::{APIResponse, Lastfm};
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok();
+    let api_key = std::env::var("API_KEY").expect("API_KEY env variable is required");
+    let api_secret = std::env::var("API_SECRET").expect("API_SECRET env variable is required");
+    let username = std::env::var("USERNAME").expect("USERNAME env variable is required");
+    let password = std::env::var("PASSWORD").expect("PASSWORD env variable is required");
+
+    let mut lastfm = Lastfm::builder()
+        .api_key(api_key)
+        .api_secret(api_secret)
+        .build()?;
+
+    let response = lastfm
+        .auth()
+        .get_mobile_session()
+        .username(&username)
+        .password(&password)
+        .send()
+        .await?;
+
+    match response {
+        APIResponse::Success(value) => {
+            println!("Session for {}: {}", value.session.name, value.session.key);
+            lastfm.set_sk(value.session.key);
+        }
+        APIResponse::Error(err) => {
+            println!("Error: {} - {}", err.error, err.message);
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use dotenv::dotenv;
-use lastfm_rust::{APIResponse, Lastfm};
-use std::error::Error;
+use lastfm_rust::Lastfm;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -8,30 +8,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let api_key = std::env::var("API_KEY").expect("API_KEY env variable is required");
     let api_secret = std::env::var("API_SECRET").expect("API_SECRET env variable is required");
 
-    let lastfm = Lastfm::builder()
+    let mut lastfm = Lastfm::builder()
         .api_key(api_key)
         .api_secret(api_secret)
         .build()?;
 
-    // Request token
-    let response = lastfm.auth().get_token().send().await?;
+    // Request a token and build the authorization URL
+    let flow = lastfm
+        .auth()
+        .start_web_auth()
+        .await?
+        .interval(Duration::from_secs(5));
 
-    let token = match response {
-        APIResponse::Success(value) => value.token,
-        APIResponse::Error(err) => {
-            return Err(Box::new(err) as Box<dyn Error>);
-        }
-    };
+    println!("Please authorize the application by visiting the following URL:");
+    println!("{}", flow.authorization_url());
 
-    println!("Token: {}", token);
+    // Wait until the user approves the token
+    let session = flow.wait_for_session().await?;
 
-    // Authorize the token
-    lastfm.auth().pls_authorize(token.to_string());
-
-    // Get session key
-    let response = lastfm.auth().get_session().token(&token).send().await?;
-
-    println!("Session: {}", response);
+    println!("Session key for {}: {}", session.name, session.key);
+    lastfm.set_session(&session);
 
     Ok(())
 }
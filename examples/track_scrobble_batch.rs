@@ -0,0 +1,47 @@
+use dotenv::dotenv;
+use lastfm_rust::{Lastfm, ScrobbleEntry};
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok();
+    let api_key = std::env::var("API_KEY").expect("API_KEY env variable is required");
+    let api_secret = std::env::var("API_SECRET").expect("API_SECRET env variable is required");
+    let sk = std::env::var("SK").expect("SK env variable is required");
+
+    let lastfm = Lastfm::builder()
+        .api_key(api_key)
+        .api_secret(api_secret)
+        .session_key(sk)
+        .build()?;
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let batch = lastfm
+        .track()
+        .scrobble_batch()
+        .entry(
+            ScrobbleEntry::new("Billie Eilish", "CHIHIRO", now - 600).album("HIT ME HARD AND SOFT"),
+        )
+        .entry(
+            ScrobbleEntry::new("Billie Eilish", "LUNCH", now - 300).album("HIT ME HARD AND SOFT"),
+        )
+        .send()
+        .await?;
+
+    for result in &batch.submitted {
+        println!(
+            "{} - {}: accepted={} {}",
+            result.entry.artist,
+            result.entry.track,
+            result.accepted,
+            result.scrobble.ignored_message.code
+        );
+    }
+    if let (Some(index), Some(err)) = (batch.failed_from, &batch.error) {
+        println!("Entries from {} were not submitted: {}", index, err);
+    }
+
+    Ok(())
+}
@@ -27,28 +27,28 @@ impl<'a> Album<'a> {
     }
 
     /// Creates a request to get tags for the album.
-    pub fn get_tags(&mut self) -> AlbumGetTags<'_> {
+    pub fn get_tags(&mut self) -> AlbumGetTags<'a> {
         AlbumGetTags::new(self.lastfm)
     }
 
     /// Creates a request to add tags to the album.
-    pub fn add_tags(&mut self) -> AlbumAddTagsRequest<'_> {
+    pub fn add_tags(&mut self) -> AlbumAddTagsRequest<'a> {
         AlbumAddTagsRequest::new(self.lastfm)
     }
 
-    pub fn get_info(&mut self) -> AlbumGetInfo<'_> {
+    pub fn get_info(&mut self) -> AlbumGetInfo<'a> {
         AlbumGetInfo::new(self.lastfm)
     }
 
-    pub fn get_top_tags(&mut self) -> AlbumGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> AlbumGetTopTags<'a> {
         AlbumGetTopTags::new(self.lastfm)
     }
 
-    pub fn remove_tag(&mut self) -> AlbumRemoveTag<'_> {
+    pub fn remove_tag(&mut self) -> AlbumRemoveTag<'a> {
         AlbumRemoveTag::new(self.lastfm)
     }
 
-    pub fn search(&mut self) -> AlbumSearch<'_> {
+    pub fn search(&mut self) -> AlbumSearch<'a> {
         AlbumSearch::new(self.lastfm)
     }
 }
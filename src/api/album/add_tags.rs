@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -50,29 +50,10 @@ impl<'a> AlbumAddTagsRequest<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() {
-            return Err(Error::Generic("Field 'artist' is required.".to_string()));
-        }
-
-        if self.album.is_none() {
-            return Err(Error::Generic("Field 'album' is required.".to_string()));
-        }
-
-        if self.tags.is_none() {
-            return Err(Error::Generic("Field 'tags' is required.".to_string()));
-        }
-
-        let tag_count = self
-            .tags
-            .as_ref()
-            .unwrap()
-            .split(',')
-            .collect::<Vec<_>>()
-            .len();
-        if tag_count > 10 {
-            return Err(Error::Generic("Cannot exceed 10 tags.".to_string()));
-        }
-
+        validation::required("artist", &self.artist)?;
+        validation::required("album", &self.album)?;
+        validation::required("tags", &self.tags)?;
+        validation::max_items("tags", &self.tags, validation::MAX_TAGS)?;
         Ok(())
     }
 
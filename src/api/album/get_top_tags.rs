@@ -1,5 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder}, APIResponse, AlbumGetTopTagsResponse, Error, Lastfm, Result
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, AlbumGetTopTagsResponse, Lastfm, Result,
 };
 use reqwest::Method;
 
@@ -51,11 +52,13 @@ impl<'a> AlbumGetTopTags<'a> {
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none() || self.album.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or both 'artist' and 'album' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (
+                &["artist", "album"],
+                validation::present(&self.artist) && validation::present(&self.album),
+            ),
+        ])?;
         Ok(())
     }
 
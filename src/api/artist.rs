@@ -35,52 +35,52 @@ impl<'a> Artist<'a> {
     }
 
     /// Creates a request to get tags for the artist.
-    pub fn get_tags(&mut self) -> ArtistGetTags<'_> {
+    pub fn get_tags(&mut self) -> ArtistGetTags<'a> {
         ArtistGetTags::new(self.lastfm)
     }
 
     /// Creates a request to get corrections for the artist.
-    pub fn get_correction(&mut self) -> ArtistGetCorrection<'_> {
+    pub fn get_correction(&mut self) -> ArtistGetCorrection<'a> {
         ArtistGetCorrection::new(self.lastfm)
     }
 
     /// Creates a request to get info for the artist.
-    pub fn get_info(&mut self) -> ArtistGetInfo<'_> {
+    pub fn get_info(&mut self) -> ArtistGetInfo<'a> {
         ArtistGetInfo::new(self.lastfm)
     }
 
     /// Creates a request to get similar artist to this artist.
-    pub fn get_similar(&mut self) -> ArtistGetSimilar<'_> {
+    pub fn get_similar(&mut self) -> ArtistGetSimilar<'a> {
         ArtistGetSimilar::new(self.lastfm)
     }
 
     /// Creates a request to add tags to the artist.
-    pub fn add_tags(&mut self) -> ArtistAddTagsRequest<'_> {
+    pub fn add_tags(&mut self) -> ArtistAddTagsRequest<'a> {
         ArtistAddTagsRequest::new(self.lastfm)
     }
 
     /// Creates a request to get top albums for the artist.
-    pub fn get_top_albums(&mut self) -> ArtistGetTopAlbums<'_> {
+    pub fn get_top_albums(&mut self) -> ArtistGetTopAlbums<'a> {
         ArtistGetTopAlbums::new(self.lastfm)
     }
 
     /// Creates a request to get top tags for the artist.
-    pub fn get_top_tags(&mut self) -> ArtistGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> ArtistGetTopTags<'a> {
         ArtistGetTopTags::new(self.lastfm)
     }
 
     /// Creates a request to get top tracks for the artist.
-    pub fn get_top_tracks(&mut self) -> ArtistGetTopTracks<'_> {
+    pub fn get_top_tracks(&mut self) -> ArtistGetTopTracks<'a> {
         ArtistGetTopTracks::new(self.lastfm)
     }
 
     /// Creates a request to remove a tag for the artist.
-    pub fn remove_tag(&mut self) -> ArtistRemoveTag<'_> {
+    pub fn remove_tag(&mut self) -> ArtistRemoveTag<'a> {
         ArtistRemoveTag::new(self.lastfm)
     }
 
     /// Creates a request to search a artist.
-    pub fn search(&mut self) -> ArtistSearch<'_> {
+    pub fn search(&mut self) -> ArtistSearch<'a> {
         ArtistSearch::new(self.lastfm)
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -30,10 +30,7 @@ impl<'a> ArtistGetCorrection<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() {
-            return Err(Error::Generic("Field 'artist' is required.".to_string()));
-        }
-
+        validation::required("artist", &self.artist)?;
         Ok(())
     }
 
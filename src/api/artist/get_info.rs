@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, ArtistGetInfoResponse, Lastfm, Result,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct ArtistGetInfo<'a> {
@@ -63,16 +62,15 @@ impl<'a> ArtistGetInfo<'a> {
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (&["artist"], validation::present(&self.artist)),
+        ])?;
         Ok(())
     }
 
     /// Sends the request and retrieves the info for the artist.
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<ArtistGetInfoResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, ArtistGetSimilarResponse, Lastfm, Result,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct ArtistGetSimilar<'a> {
@@ -11,7 +10,7 @@ pub struct ArtistGetSimilar<'a> {
     pub artist: Option<String>,
     pub mbid: Option<String>,
     pub autocorrect: Option<bool>,
-    pub limit: Option<i64>,
+    pub limit: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -40,7 +39,7 @@ impl<'a> ArtistGetSimilar<'a> {
     }
 
     /// Limit the number of similar artists returned
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
@@ -53,16 +52,16 @@ impl<'a> ArtistGetSimilar<'a> {
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (&["artist"], validation::present(&self.artist)),
+        ])?;
+        validation::limit(&self.limit)?;
         Ok(())
     }
 
     /// Sends the request and retrieves the tags for the artist.
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<ArtistGetSimilarResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
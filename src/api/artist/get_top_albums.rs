@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, ArtistGetTopAlbumsResponse, Lastfm, Result,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct ArtistGetTopAlbums<'a> {
@@ -11,8 +10,8 @@ pub struct ArtistGetTopAlbums<'a> {
     pub artist: Option<String>,
     pub mbid: Option<String>,
     pub autocorrect: Option<bool>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -48,35 +47,36 @@ impl<'a> ArtistGetTopAlbums<'a> {
     }
 
     /// The number of results to fetch per page. Defaults to 30.
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
     /// The page number to fetch. Defaults to first page.
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (&["artist"], validation::present(&self.artist)),
+        ])?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
     /// Sends the request and retrieves the tags for the artist.
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<ArtistGetTopAlbumsResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.expect("The artist name is required!"))
+            .add_optional("artist", self.artist)
             .add_optional("mbid", self.mbid)
             .add_optional("autocorrect", self.autocorrect.map(|b| b.to_string()))
             .add_optional("limit", self.limit.map(|b| b.to_string()))
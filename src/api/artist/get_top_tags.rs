@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -45,11 +45,10 @@ impl<'a> ArtistGetTopTags<'a> {
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (&["artist"], validation::present(&self.artist)),
+        ])?;
         Ok(())
     }
 
@@ -60,7 +59,7 @@ impl<'a> ArtistGetTopTags<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.expect("The artist name is required!"))
+            .add_optional("artist", self.artist)
             .add_optional("mbid", self.mbid)
             .add_optional("autocorrect", self.autocorrect.map(|b| b.to_string()));
 
@@ -1,16 +1,15 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, ArtistSearchResponse, Lastfm, Result,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct ArtistSearch<'a> {
     lastfm: &'a Lastfm,
     pub artist: Option<String>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -32,34 +31,33 @@ impl<'a> ArtistSearch<'a> {
     }
 
     /// The number of results to fetch per page. Defaults to 30.
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
     /// The page number to fetch. Defaults to first page.
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() || self.artist.as_ref().unwrap().is_empty() {
-            return Err(Error::Generic("The artist name is required.".to_string()));
-        }
-
+        validation::required("artist", &self.artist)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
     /// Sends the request and retrieves the tags for the artist.
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<ArtistSearchResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.expect("The artist name is required!"))
+            .add_optional("artist", self.artist)
             .add_optional("limit", self.limit.map(|b| b.to_string()))
             .add_optional("page", self.page.map(|b| b.to_string()));
 
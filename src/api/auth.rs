@@ -1,9 +1,17 @@
+#[cfg(feature = "callback-server")]
+mod callback;
+mod get_mobile_session;
 mod get_session;
 mod get_token;
+mod web_auth;
 
-use crate::Lastfm;
+use crate::{APIResponse, Error, Lastfm, Result};
+#[cfg(feature = "callback-server")]
+pub use callback::CallbackLogin;
+pub use get_mobile_session::AuthGetMobileSession;
 pub use get_session::AuthGetSession;
 pub use get_token::AuthGetToken;
+pub use web_auth::{AuthStatus, WebAuthFlow};
 
 #[derive(Debug)]
 pub struct Auth<'a> {
@@ -19,32 +27,63 @@ impl<'a> Auth<'a> {
         }
     }
 
-    pub fn get_token(&mut self) -> AuthGetToken<'_> {
+    pub fn get_token(&mut self) -> AuthGetToken<'a> {
         AuthGetToken::new(self.lastfm)
     }
 
-    pub fn get_session(&mut self) -> AuthGetSession<'_> {
+    pub fn get_session(&mut self) -> AuthGetSession<'a> {
         AuthGetSession::new(self.lastfm)
     }
 
-    pub fn get_mobile_session(&mut self) -> AuthGetSession<'_> {
-        //TODO: get mobile session
-        todo!()
+    pub fn get_mobile_session(&mut self) -> AuthGetMobileSession<'a> {
+        AuthGetMobileSession::new(self.lastfm)
     }
 
-    pub fn pls_authorize(&self, token: String) {
-        let auth_url = format!(
-            "https://www.last.fm/api/auth?api_key={}&token={}",
+    /// Builds the URL the user must visit to approve `token`.
+    pub fn authorization_url(&self, token: &str) -> String {
+        format!(
+            "{}?api_key={}&token={}",
+            self.lastfm.get_auth_url(),
             self.api_key,
             token.replace("\"", "")
-        );
+        )
+    }
+
+    /// Starts a non-blocking web authentication flow for an existing token.
+    pub fn web_auth(&self, token: &str) -> WebAuthFlow<'a> {
+        WebAuthFlow::new(self.lastfm, token, self.authorization_url(token))
+    }
+
+    /// Requests a new token and starts a web authentication flow for it.
+    pub async fn start_web_auth(&self) -> Result<WebAuthFlow<'a>> {
+        match AuthGetToken::new(self.lastfm).send().await? {
+            APIResponse::Success(response) => Ok(self.web_auth(&response.token)),
+            APIResponse::Error(err) => Err(Error::ApiError(err)),
+        }
+    }
+
+    /// Starts a web authentication flow that receives the token on a local callback listener.
+    ///
+    /// `addr` is the local address to listen on, e.g. `"127.0.0.1:0"` for any free port.
+    #[cfg(feature = "callback-server")]
+    pub async fn callback_login<A>(&self, addr: A) -> Result<CallbackLogin<'a>>
+    where
+        A: tokio::net::ToSocketAddrs,
+    {
+        CallbackLogin::bind(self.lastfm, addr, self.lastfm.get_auth_url()).await
+    }
+
+    #[deprecated(note = "blocks on stdin; use `web_auth` or `start_web_auth` and poll instead")]
+    pub fn pls_authorize(&self, token: String) {
+        let auth_url = self.authorization_url(&token);
 
         println!("Please authorize the application by visiting the following URL:");
         println!("{}", auth_url);
         println!("\nOnce you have authorized the app, press Enter to continue...");
 
         let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+        // Any input, or none at all, continues the flow.
+        let _ = std::io::stdin().read_line(&mut input);
 
         println!("Thank you! Continuing...");
     }
@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use reqwest::Url;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::task::JoinSet;
+use tokio::time::{sleep, timeout};
+
+use super::AuthGetSession;
+use crate::{APIResponse, Error, Lastfm, Result, Session};
+
+const CALLBACK_PATH: &str = "/callback";
+
+const SUCCESS_PAGE: &str = "<html><body><p>Authorization complete. \
+You can close this window and return to the application.</p></body></html>";
+
+/// Completes the web authentication flow by catching Last.fm's redirect on a local port.
+///
+/// Open [`CallbackLogin::authorization_url`] in the user's browser, then await
+/// [`CallbackLogin::wait_for_session`]. Once the user approves the application, Last.fm
+/// redirects to the local listener with a `token` query parameter, which is exchanged for
+/// a session through `auth.getSession`.
+#[derive(Debug)]
+pub struct CallbackLogin<'a> {
+    lastfm: &'a Lastfm,
+    listener: TcpListener,
+    authorization_url: String,
+    timeout: Duration,
+}
+
+impl<'a> CallbackLogin<'a> {
+    pub(crate) async fn bind<A: ToSocketAddrs>(
+        lastfm: &'a Lastfm,
+        addr: A,
+        auth_url: &str,
+    ) -> Result<CallbackLogin<'a>> {
+        let listener = TcpListener::bind(addr).await?;
+        let callback_url = format!("http://{}{}", listener.local_addr()?, CALLBACK_PATH);
+
+        let authorization_url = Url::parse_with_params(
+            auth_url,
+            &[("api_key", lastfm.get_api_key()), ("cb", callback_url)],
+        )
+        .map_err(|err| Error::Generic(err.to_string()))?;
+
+        Ok(CallbackLogin {
+            lastfm,
+            listener,
+            authorization_url: authorization_url.to_string(),
+            timeout: Duration::from_secs(5 * 60),
+        })
+    }
+
+    /// How long to wait for the user before giving up. Defaults to 5 minutes.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The URL the user must visit to approve the application.
+    pub fn authorization_url(&self) -> &str {
+        &self.authorization_url
+    }
+
+    /// Waits for the redirect, then exchanges the received token for a session.
+    pub async fn wait_for_session(self) -> Result<Session> {
+        let token = timeout(self.timeout, self.receive_token())
+            .await
+            .map_err(|_| Error::AuthorizationTimeout)??;
+
+        match AuthGetSession::new(self.lastfm)
+            .token(&token)
+            .send()
+            .await?
+        {
+            APIResponse::Success(response) => Ok(response.session),
+            APIResponse::Error(err) => Err(Error::ApiError(err)),
+        }
+    }
+
+    /// Accepts connections until one of them carries the `token` query parameter.
+    ///
+    /// Connections are served concurrently, so an idle preconnect from the browser does not
+    /// hold up the redirect, and a connection that fails is dropped without ending the login.
+    async fn receive_token(&self) -> Result<String> {
+        let mut connections = JoinSet::new();
+
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        connections.spawn(serve(stream));
+                    }
+                    // Usually a connection reset before it was accepted. Back off in case
+                    // the error persists, e.g. when out of file descriptors.
+                    Err(_) => sleep(Duration::from_millis(50)).await,
+                },
+                Some(served) = connections.join_next() => {
+                    if let Ok(Some(token)) = served {
+                        return Ok(token);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Answers a single connection, returning the token if it carried one.
+async fn serve(mut stream: TcpStream) -> Option<String> {
+    // Failing to answer the browser does not matter once the token has been read.
+    match read_token(&mut stream).await {
+        Ok(Some(token)) => {
+            let _ = respond(&mut stream, "200 OK", SUCCESS_PAGE).await;
+            Some(token)
+        }
+        Ok(None) => {
+            let _ = respond(&mut stream, "404 Not Found", "").await;
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Reads the request head and extracts the token from a `GET /callback?token=...` request.
+async fn read_token(stream: &mut TcpStream) -> Result<Option<String>> {
+    let mut buffer = vec![0; 8192];
+    let mut read = 0;
+
+    while read < buffer.len() {
+        let n = stream.read(&mut buffer[read..]).await?;
+        if n == 0 {
+            break;
+        }
+        read += n;
+        if buffer[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+    }
+
+    let head = String::from_utf8_lossy(&buffer[..read]);
+    let target = match head.lines().next().and_then(|line| line.split(' ').nth(1)) {
+        Some(target) => target,
+        None => return Ok(None),
+    };
+
+    let url = match Url::parse(&format!("http://localhost{}", target)) {
+        Ok(url) if url.path() == CALLBACK_PATH => url,
+        _ => return Ok(None),
+    };
+
+    Ok(url
+        .query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, value)| value.into_owned()))
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_receive_token_from_redirect() {
+        let lastfm = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .build()
+            .unwrap();
+        let login = lastfm.auth().callback_login("127.0.0.1:0").await.unwrap();
+
+        let url = Url::parse(login.authorization_url()).unwrap();
+        let callback = url
+            .query_pairs()
+            .find(|(key, _)| key == "cb")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+
+        let client = lastfm.get_client().clone();
+        let redirect = tokio::spawn(async move {
+            client
+                .get(format!("{}?token=abc123", callback))
+                .send()
+                .await
+        });
+
+        assert_eq!(login.receive_token().await.unwrap(), "abc123");
+        assert!(redirect.await.unwrap().unwrap().status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_bad_connections_do_not_abort_login() {
+        let lastfm = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .build()
+            .unwrap();
+        let login = lastfm.auth().callback_login("127.0.0.1:0").await.unwrap();
+        let addr = login.listener.local_addr().unwrap();
+
+        let browser = tokio::spawn(async move {
+            // A preconnect that never sends a request and stays open.
+            let idle = TcpStream::connect(addr).await.unwrap();
+
+            // A connection closed halfway through the request head.
+            let mut closed = TcpStream::connect(addr).await.unwrap();
+            closed.write_all(b"GET /call").await.unwrap();
+            drop(closed);
+
+            // A request for something other than the callback.
+            let mut favicon = TcpStream::connect(addr).await.unwrap();
+            favicon
+                .write_all(b"GET /favicon.ico HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                .await
+                .unwrap();
+            drop(favicon);
+
+            let mut redirect = TcpStream::connect(addr).await.unwrap();
+            redirect
+                .write_all(b"GET /callback?token=abc123 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                .await
+                .unwrap();
+            let mut response = String::new();
+            redirect.read_to_string(&mut response).await.unwrap();
+            drop(idle);
+            response
+        });
+
+        let token = timeout(Duration::from_secs(5), login.receive_token())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(token, "abc123");
+        assert!(browser.await.unwrap().starts_with("HTTP/1.1 200 OK"));
+    }
+}
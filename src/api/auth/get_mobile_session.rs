@@ -0,0 +1,130 @@
+use crate::{
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, AuthGetMobileSessionResponse, Lastfm, Result,
+};
+use reqwest::Method;
+
+#[derive(Debug, Clone)]
+pub struct AuthGetMobileSession<'a> {
+    lastfm: &'a Lastfm,
+    username: Option<String>,
+    password: Option<String>,
+    method: LastfmMethod,
+}
+
+impl<'a> AuthGetMobileSession<'a> {
+    pub(crate) fn new(lastfm: &'a Lastfm) -> Self {
+        AuthGetMobileSession {
+            lastfm,
+            username: None,
+            password: None,
+            method: LastfmMethod::AuthGetMobileSession,
+        }
+    }
+
+    /// The username or email address of the user to authenticate.
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+
+    /// The password of the user to authenticate.
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        validation::required("username", &self.username)?;
+        validation::required("password", &self.password)?;
+        Ok(())
+    }
+
+    /// Sends the request and retrieves a session for the user.
+    pub async fn send(self) -> Result<APIResponse<AuthGetMobileSessionResponse>> {
+        self.validate()?;
+
+        let mut builder = ParameterBuilder::new();
+
+        builder = builder
+            .add_optional("username", self.username)
+            .add_optional("password", self.password);
+
+        let mut params = builder.build();
+
+        let response = self
+            .lastfm
+            .send_request(self.method, &mut params, Method::POST)
+            .await?;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lastfm::signature;
+    use crate::transport::testing::lastfm_with;
+    use crate::{APIResponse, Error, LastfmMethod, MockTransport, ValidationError};
+    use reqwest::Method;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_signed_post_with_password() {
+        let transport = Arc::new(MockTransport::new().respond(
+            LastfmMethod::AuthGetMobileSession,
+            json!({
+                "session": { "name": "uppercase_", "key": "session_key", "subscriber": 1 }
+            }),
+        ));
+        let lastfm = lastfm_with(transport.clone());
+
+        let response = lastfm
+            .auth()
+            .get_mobile_session()
+            .username("uppercase_")
+            .password("hunter2")
+            .send()
+            .await
+            .unwrap();
+
+        match response {
+            APIResponse::Success(response) => {
+                assert_eq!(response.session.name, "uppercase_");
+                assert_eq!(response.session.key, "session_key");
+                assert!(response.session.subscriber);
+            }
+            APIResponse::Error(err) => panic!("unexpected API error: {}", err.message),
+        }
+
+        assert_eq!(transport.http_methods(), [Method::POST]);
+        let mut request = transport.last_request().unwrap();
+        assert_eq!(request["method"], "auth.getMobileSession");
+        assert_eq!(request["username"], "uppercase_");
+        assert_eq!(request["password"], "hunter2");
+        assert!(!request.contains_key("sk"));
+
+        let api_sig = request.remove("api_sig").unwrap();
+        request.remove("format");
+        assert_eq!(api_sig, signature(&request, "test_api_secret"));
+        request.insert("password".to_string(), "other".to_string());
+        assert_ne!(api_sig, signature(&request, "test_api_secret"));
+    }
+
+    #[tokio::test]
+    async fn test_requires_username_and_password() {
+        let lastfm = lastfm_with(Arc::new(MockTransport::new()));
+
+        let result = lastfm
+            .auth()
+            .get_mobile_session()
+            .username("uppercase_")
+            .send()
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::Missing("password")))
+        ));
+    }
+}
@@ -1,22 +1,9 @@
-use serde_json::Value;
+use crate::api::validation;
+use reqwest::Method;
 use std::collections::HashMap;
 
 use crate::api::LastfmMethod;
-use crate::error::ApiError;
-use crate::{Error, Lastfm, Result};
-
-// #[derive(Debug, Deserialize)]
-// pub struct SessionResponse {
-//     #[serde(rename = "session")]
-//     pub session: Session,
-// }
-//
-// #[derive(Debug, Deserialize)]
-// pub struct Session {
-//     pub name: String,
-//     pub key: String,
-//     pub subscriber: i32,
-// }
+use crate::{APIResponse, AuthGetSessionResponse, Lastfm, Result};
 
 #[derive(Debug)]
 pub struct AuthGetSession<'a> {
@@ -40,42 +27,35 @@ impl<'a> AuthGetSession<'a> {
         self
     }
 
-    fn get_token(&self) -> String {
-        self.token.clone().expect("A token is required!")
+    fn validate(&self) -> Result<()> {
+        validation::required("token", &self.token)?;
+        Ok(())
     }
 
     pub fn request_session_params(&self) -> HashMap<String, String> {
         let mut params: HashMap<String, String> = HashMap::new();
 
         params.insert("api_key".to_string(), self.lastfm.get_api_key());
-        params.insert("token".to_string(), self.get_token());
+        if let Some(token) = &self.token {
+            params.insert("token".to_string(), token.clone());
+        }
 
         params
     }
 
-    pub async fn send(self) -> Result<Value> {
-        let mut session_params = self.request_session_params();
+    /// Sends the request and exchanges the authorized token for a session.
+    ///
+    /// Pass the returned session to [`Lastfm::set_session`] to authenticate the client.
+    pub async fn send(self) -> Result<APIResponse<AuthGetSessionResponse>> {
+        self.validate()?;
 
-        session_params.insert("method".to_string(), self.method.to_string());
-        let api_sig = self.lastfm.sign_api(&mut session_params);
-        session_params.insert("api_sig".to_string(), api_sig);
-        session_params.insert("format".to_string(), "json".to_string());
+        let mut session_params = self.request_session_params();
 
         let response = self
             .lastfm
-            .get_client()
-            .get(self.lastfm.get_base_url())
-            .query(&session_params)
-            .send()
+            .send_request(self.method, &mut session_params, Method::GET)
             .await?;
 
-        let json_response: Value = response.json().await?;
-
-        if json_response.get("error").is_some() {
-            let api_error: ApiError = serde_json::from_value(json_response)?;
-            return Err(Error::ApiError(api_error));
-        }
-
-        Ok(json_response)
+        Ok(response)
     }
 }
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+
+use super::AuthGetSession;
+use crate::{APIResponse, ApiErrorCode, Error, Lastfm, Result, Session};
+
+/// The state of a token after a single [`WebAuthFlow::poll`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthStatus {
+    /// The user has not approved the token yet.
+    Pending,
+    /// The user approved the token, which was exchanged for this session.
+    Approved(Session),
+    /// The token expired before it was approved. A new flow must be started.
+    Expired,
+}
+
+/// Drives the desktop web authentication flow without blocking on user input.
+///
+/// Show [`WebAuthFlow::authorization_url`] to the user, then await
+/// [`WebAuthFlow::wait_for_session`], which polls `auth.getSession` until the token is approved.
+#[derive(Debug, Clone)]
+pub struct WebAuthFlow<'a> {
+    lastfm: &'a Lastfm,
+    token: String,
+    authorization_url: String,
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl<'a> WebAuthFlow<'a> {
+    pub(crate) fn new(lastfm: &'a Lastfm, token: &str, authorization_url: String) -> Self {
+        WebAuthFlow {
+            lastfm,
+            token: token.replace("\"", ""),
+            authorization_url,
+            interval: Duration::from_secs(3),
+            timeout: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// How long to wait between two `auth.getSession` attempts. Defaults to 3 seconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// How long to wait for the user before giving up. Defaults to 5 minutes.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// The URL the user must visit to approve the token.
+    pub fn authorization_url(&self) -> &str {
+        &self.authorization_url
+    }
+
+    /// Tries to exchange the token for a session once.
+    pub async fn poll(&self) -> Result<AuthStatus> {
+        let request = AuthGetSession::new(self.lastfm).token(&self.token);
+
+        match request.send().await? {
+            APIResponse::Success(response) => Ok(AuthStatus::Approved(response.session)),
+            APIResponse::Error(err) => match err.code() {
+                ApiErrorCode::UnauthorizedToken => Ok(AuthStatus::Pending),
+                ApiErrorCode::TokenExpired => Ok(AuthStatus::Expired),
+                _ => Err(Error::ApiError(err)),
+            },
+        }
+    }
+
+    /// Polls `auth.getSession` until the user approves the token and returns the session.
+    ///
+    /// An expired token is returned as `Error::TokenExpired`, and running out of time as
+    /// `Error::AuthorizationTimeout`.
+    pub async fn wait_for_session(self) -> Result<Session> {
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            match self.poll().await? {
+                AuthStatus::Approved(session) => return Ok(session),
+                AuthStatus::Expired => return Err(Error::TokenExpired),
+                AuthStatus::Pending => {}
+            }
+
+            if Instant::now() + self.interval > deadline {
+                return Err(Error::AuthorizationTimeout);
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::lastfm_with;
+    use crate::{LastfmMethod, MockTransport};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn session() -> serde_json::Value {
+        json!({ "session": { "name": "uppercase_", "key": "session_key", "subscriber": 0 } })
+    }
+
+    fn api_error(code: ApiErrorCode) -> serde_json::Value {
+        json!({ "error": code.code(), "message": code.to_string() })
+    }
+
+    #[tokio::test]
+    async fn test_poll_reports_each_state() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond_once(
+                    LastfmMethod::AuthGetSession,
+                    api_error(ApiErrorCode::UnauthorizedToken),
+                )
+                .respond_once(LastfmMethod::AuthGetSession, session())
+                .respond_once(
+                    LastfmMethod::AuthGetSession,
+                    api_error(ApiErrorCode::TokenExpired),
+                )
+                .respond_once(
+                    LastfmMethod::AuthGetSession,
+                    api_error(ApiErrorCode::InvalidApiKey),
+                ),
+        );
+        let lastfm = lastfm_with(transport.clone());
+        let flow = lastfm.auth().web_auth("token");
+
+        assert_eq!(flow.poll().await.unwrap(), AuthStatus::Pending);
+        match flow.poll().await.unwrap() {
+            AuthStatus::Approved(session) => assert_eq!(session.key, "session_key"),
+            status => panic!("expected an approved session, got {:?}", status),
+        }
+        assert_eq!(flow.poll().await.unwrap(), AuthStatus::Expired);
+        assert!(matches!(flow.poll().await, Err(Error::ApiError(err)) if err.error == 10));
+
+        let request = transport.last_request().unwrap();
+        assert_eq!(request["method"], "auth.getSession");
+        assert_eq!(request["token"], "token");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_session_polls_until_approved() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond_once(
+                    LastfmMethod::AuthGetSession,
+                    api_error(ApiErrorCode::UnauthorizedToken),
+                )
+                .respond(LastfmMethod::AuthGetSession, session()),
+        );
+        let lastfm = lastfm_with(transport.clone());
+
+        let session = lastfm
+            .auth()
+            .web_auth("token")
+            .interval(Duration::from_millis(1))
+            .wait_for_session()
+            .await
+            .unwrap();
+        assert_eq!(session.name, "uppercase_");
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_session_stops_on_expiry_and_timeout() {
+        let expired = Arc::new(MockTransport::new().respond(
+            LastfmMethod::AuthGetSession,
+            api_error(ApiErrorCode::TokenExpired),
+        ));
+        let result = lastfm_with(expired)
+            .auth()
+            .web_auth("token")
+            .wait_for_session()
+            .await;
+        assert!(matches!(result, Err(Error::TokenExpired)));
+
+        let pending = Arc::new(MockTransport::new().respond(
+            LastfmMethod::AuthGetSession,
+            api_error(ApiErrorCode::UnauthorizedToken),
+        ));
+        let result = lastfm_with(pending)
+            .auth()
+            .web_auth("token")
+            .interval(Duration::from_millis(1))
+            .timeout(Duration::from_millis(20))
+            .wait_for_session()
+            .await;
+        assert!(matches!(result, Err(Error::AuthorizationTimeout)));
+    }
+}
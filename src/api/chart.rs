@@ -21,17 +21,17 @@ impl<'a> Chart<'a> {
     }
 
     /// Creates a request to get top artists for the artist.
-    pub fn get_top_artists(&mut self) -> ChartGetTopArtists<'_> {
+    pub fn get_top_artists(&mut self) -> ChartGetTopArtists<'a> {
         ChartGetTopArtists::new(self.lastfm)
     }
 
     /// Creates a request to get top tags for the artist.
-    pub fn get_top_tags(&mut self) -> ChartGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> ChartGetTopTags<'a> {
         ChartGetTopTags::new(self.lastfm)
     }
 
     /// Creates a request to get top tracks for the artist.
-    pub fn get_top_tracks(&mut self) -> ChartGetTopTracks<'_> {
+    pub fn get_top_tracks(&mut self) -> ChartGetTopTracks<'a> {
         ChartGetTopTracks::new(self.lastfm)
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -8,8 +8,8 @@ use serde_json::Value;
 #[derive(Debug, Clone)]
 pub struct ChartGetTopArtists<'a> {
     lastfm: &'a Lastfm,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -24,19 +24,21 @@ impl<'a> ChartGetTopArtists<'a> {
     }
 
     /// The number of results to fetch per page. Defaults to 50.
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
     /// The page number to fetch. Defaults to first page.
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
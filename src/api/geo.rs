@@ -19,12 +19,12 @@ impl<'a> Geo<'a> {
     }
 
     /// Get the most popular artists on Last.fm by country
-    pub fn get_top_artists(&mut self) -> GeoGetTopArtists<'_> {
+    pub fn get_top_artists(&mut self) -> GeoGetTopArtists<'a> {
         GeoGetTopArtists::new(self.lastfm)
     }
 
     /// Get the most popular tracks on Last.fm by country
-    pub fn get_top_tracks(&mut self) -> GeoGetTopTracks<'_> {
+    pub fn get_top_tracks(&mut self) -> GeoGetTopTracks<'a> {
         GeoGetTopTracks::new(self.lastfm)
     }
 }
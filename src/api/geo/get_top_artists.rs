@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -9,8 +9,8 @@ use serde_json::Value;
 pub struct GeoGetTopArtists<'a> {
     lastfm: &'a Lastfm,
     pub country: Option<String>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -31,19 +31,22 @@ impl<'a> GeoGetTopArtists<'a> {
     }
 
     /// The number of results to fetch per page. Defaults to 50.
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
     /// The page number to fetch. Defaults to first page.
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
+        validation::required("country", &self.country)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
@@ -54,10 +57,7 @@ impl<'a> GeoGetTopArtists<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add(
-                "country",
-                self.country.expect("The country name is required!"),
-            )
+            .add_optional("country", self.country)
             .add_optional("limit", self.limit.map(|b| b.to_string()))
             .add_optional("page", self.page.map(|b| b.to_string()));
 
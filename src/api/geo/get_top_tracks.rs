@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -10,8 +10,8 @@ pub struct GeoGetTopTracks<'a> {
     lastfm: &'a Lastfm,
     pub country: Option<String>,
     pub location: Option<String>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -38,19 +38,22 @@ impl<'a> GeoGetTopTracks<'a> {
     }
 
     /// The number of results to fetch per page. Defaults to 50.
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
     /// The page number to fetch. Defaults to first page.
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     /// Validates the request parameters.
     fn validate(&self) -> Result<()> {
+        validation::required("country", &self.country)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
@@ -61,10 +64,7 @@ impl<'a> GeoGetTopTracks<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add(
-                "country",
-                self.country.expect("The country name is required!"),
-            )
+            .add_optional("country", self.country)
             .add_optional("location", self.location)
             .add_optional("limit", self.limit.map(|b| b.to_string()))
             .add_optional("page", self.page.map(|b| b.to_string()));
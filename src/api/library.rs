@@ -17,7 +17,7 @@ impl<'a> Library<'a> {
     }
 
     /// A paginated list of all the artists in a user's library, with play counts and tag counts.
-    pub fn get_artists(&mut self) -> LibraryGetArtists<'_> {
+    pub fn get_artists(&mut self) -> LibraryGetArtists<'a> {
         LibraryGetArtists::new(self.lastfm)
     }
 }
@@ -1,4 +1,8 @@
 use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Error, Result};
 
 mod album;
 mod artist;
@@ -6,23 +10,26 @@ mod auth;
 mod chart;
 mod geo;
 mod library;
+mod pagination;
 mod parameter_builder;
 mod tag;
 mod track;
 mod user;
+mod validation;
 
 pub use album::Album;
 pub use artist::Artist;
-pub use auth::Auth;
+pub use auth::{Auth, AuthStatus};
 pub use chart::Chart;
 pub use geo::Geo;
 pub use library::Library;
+pub use pagination::{Paginated, DEFAULT_PAGE_CONCURRENCY};
 pub use parameter_builder::ParameterBuilder;
 pub use tag::Tag;
-pub use track::Track;
-pub use user::User;
+pub use track::{BatchResult, ScrobbleEntry, ScrobbleResult, Track, MAX_SCROBBLES_PER_REQUEST};
+pub use user::{Period, User, MAX_RANGE_WEEKS};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum LastfmMethod {
     // Album Methods
     AlbumAddTags,
@@ -182,6 +189,18 @@ impl fmt::Display for LastfmMethod {
     }
 }
 
+impl FromStr for LastfmMethod {
+    type Err = Error;
+
+    /// Parses a method from its API name, e.g. `"track.scrobble"`.
+    fn from_str(s: &str) -> Result<Self> {
+        LastfmMethod::ALL
+            .into_iter()
+            .find(|method| method.to_string() == s)
+            .ok_or_else(|| Error::Generic(format!("Unknown method: {}", s)))
+    }
+}
+
 impl From<LastfmMethod> for String {
     fn from(method: LastfmMethod) -> Self {
         method.to_string()
@@ -189,6 +208,67 @@ impl From<LastfmMethod> for String {
 }
 
 impl LastfmMethod {
+    /// Every method of the API, in the order they are declared.
+    pub const ALL: [LastfmMethod; 57] = [
+        LastfmMethod::AlbumAddTags,
+        LastfmMethod::AlbumGetInfo,
+        LastfmMethod::AlbumGetTags,
+        LastfmMethod::AlbumGetTopTags,
+        LastfmMethod::AlbumRemoveTag,
+        LastfmMethod::AlbumSearch,
+        LastfmMethod::ArtistAddTags,
+        LastfmMethod::ArtistGetCorrection,
+        LastfmMethod::ArtistGetInfo,
+        LastfmMethod::ArtistGetSimilar,
+        LastfmMethod::ArtistGetTags,
+        LastfmMethod::ArtistGetTopAlbums,
+        LastfmMethod::ArtistGetTopTags,
+        LastfmMethod::ArtistGetTopTracks,
+        LastfmMethod::ArtistRemoveTag,
+        LastfmMethod::ArtistSearch,
+        LastfmMethod::AuthGetMobileSession,
+        LastfmMethod::AuthGetSession,
+        LastfmMethod::AuthGetToken,
+        LastfmMethod::ChartGetTopArtists,
+        LastfmMethod::ChartGetTopTags,
+        LastfmMethod::ChartGetTopTracks,
+        LastfmMethod::GeoGetTopArtists,
+        LastfmMethod::GeoGetTopTracks,
+        LastfmMethod::LibraryGetArtists,
+        LastfmMethod::TagGetInfo,
+        LastfmMethod::TagGetSimilar,
+        LastfmMethod::TagGetTopAlbums,
+        LastfmMethod::TagGetTopArtists,
+        LastfmMethod::TagGetTopTags,
+        LastfmMethod::TagGetTopTracks,
+        LastfmMethod::TagGetWeeklyChartList,
+        LastfmMethod::TrackAddTags,
+        LastfmMethod::TrackGetCorrection,
+        LastfmMethod::TrackGetInfo,
+        LastfmMethod::TrackGetSimilar,
+        LastfmMethod::TrackGetTags,
+        LastfmMethod::TrackGetTopTags,
+        LastfmMethod::TrackLove,
+        LastfmMethod::TrackRemoveTag,
+        LastfmMethod::TrackScrobble,
+        LastfmMethod::TrackSearch,
+        LastfmMethod::TrackUnlove,
+        LastfmMethod::TrackUpdateNowPlaying,
+        LastfmMethod::UserGetFriends,
+        LastfmMethod::UserGetInfo,
+        LastfmMethod::UserGetLovedTracks,
+        LastfmMethod::UserGetPersonalTags,
+        LastfmMethod::UserGetRecentTracks,
+        LastfmMethod::UserGetTopAlbums,
+        LastfmMethod::UserGetTopArtists,
+        LastfmMethod::UserGetTopTags,
+        LastfmMethod::UserGetTopTracks,
+        LastfmMethod::UserGetWeeklyAlbumChart,
+        LastfmMethod::UserGetWeeklyArtistChart,
+        LastfmMethod::UserGetWeeklyChartList,
+        LastfmMethod::UserGetWeeklyTrackChart,
+    ];
+
     pub fn requires_auth(&self) -> bool {
         matches!(
             self,
@@ -204,4 +284,89 @@ impl LastfmMethod {
                 | LastfmMethod::TrackScrobble
         )
     }
+
+    /// How long responses for this method are cached by default, or `None` if they are not.
+    ///
+    /// Catalogue metadata changes rarely and is kept for a day, charts and listings for an hour,
+    /// and user activity for a few minutes at most.
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        const MINUTE: u64 = 60;
+        const HOUR: u64 = 60 * MINUTE;
+
+        let secs = match self {
+            LastfmMethod::AlbumAddTags
+            | LastfmMethod::AlbumRemoveTag
+            | LastfmMethod::ArtistAddTags
+            | LastfmMethod::ArtistRemoveTag
+            | LastfmMethod::TrackAddTags
+            | LastfmMethod::TrackLove
+            | LastfmMethod::TrackUnlove
+            | LastfmMethod::TrackRemoveTag
+            | LastfmMethod::TrackUpdateNowPlaying
+            | LastfmMethod::TrackScrobble
+            | LastfmMethod::AuthGetMobileSession
+            | LastfmMethod::AuthGetSession
+            | LastfmMethod::AuthGetToken => return None,
+
+            LastfmMethod::AlbumGetInfo
+            | LastfmMethod::ArtistGetInfo
+            | LastfmMethod::TrackGetInfo
+            | LastfmMethod::TagGetInfo
+            | LastfmMethod::ArtistGetCorrection
+            | LastfmMethod::TrackGetCorrection => 24 * HOUR,
+
+            LastfmMethod::AlbumGetTopTags
+            | LastfmMethod::ArtistGetSimilar
+            | LastfmMethod::ArtistGetTopAlbums
+            | LastfmMethod::ArtistGetTopTags
+            | LastfmMethod::ArtistGetTopTracks
+            | LastfmMethod::TrackGetSimilar
+            | LastfmMethod::TrackGetTopTags
+            | LastfmMethod::TagGetSimilar
+            | LastfmMethod::TagGetTopAlbums
+            | LastfmMethod::TagGetTopArtists
+            | LastfmMethod::TagGetTopTags
+            | LastfmMethod::TagGetTopTracks
+            | LastfmMethod::TagGetWeeklyChartList => 6 * HOUR,
+
+            LastfmMethod::AlbumSearch
+            | LastfmMethod::ArtistSearch
+            | LastfmMethod::TrackSearch
+            | LastfmMethod::ChartGetTopArtists
+            | LastfmMethod::ChartGetTopTags
+            | LastfmMethod::ChartGetTopTracks
+            | LastfmMethod::GeoGetTopArtists
+            | LastfmMethod::GeoGetTopTracks
+            | LastfmMethod::UserGetWeeklyAlbumChart
+            | LastfmMethod::UserGetWeeklyArtistChart
+            | LastfmMethod::UserGetWeeklyChartList
+            | LastfmMethod::UserGetWeeklyTrackChart => HOUR,
+
+            LastfmMethod::AlbumGetTags
+            | LastfmMethod::ArtistGetTags
+            | LastfmMethod::TrackGetTags
+            | LastfmMethod::LibraryGetArtists
+            | LastfmMethod::UserGetFriends
+            | LastfmMethod::UserGetInfo
+            | LastfmMethod::UserGetLovedTracks
+            | LastfmMethod::UserGetPersonalTags
+            | LastfmMethod::UserGetTopAlbums
+            | LastfmMethod::UserGetTopArtists
+            | LastfmMethod::UserGetTopTags
+            | LastfmMethod::UserGetTopTracks => 10 * MINUTE,
+
+            LastfmMethod::UserGetRecentTracks => 30,
+        };
+
+        Some(Duration::from_secs(secs))
+    }
+
+    /// Whether the request must carry an `api_sig`, with or without a session key.
+    pub fn requires_signature(&self) -> bool {
+        self.requires_auth()
+            || matches!(
+                self,
+                LastfmMethod::AuthGetMobileSession | LastfmMethod::AuthGetSession
+            )
+    }
 }
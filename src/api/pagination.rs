@@ -0,0 +1,420 @@
+//! Walking the pages of paginated methods.
+
+use std::future::{self, Future};
+
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde_json::Value;
+
+use crate::api::album::AlbumSearch;
+use crate::api::artist::{ArtistGetTopAlbums, ArtistGetTopTracks, ArtistSearch};
+use crate::api::chart::{ChartGetTopArtists, ChartGetTopTags, ChartGetTopTracks};
+use crate::api::geo::{GeoGetTopArtists, GeoGetTopTracks};
+use crate::api::library::LibraryGetArtists;
+use crate::api::tag::{TagGetTopAlbums, TagGetTopArtists, TagGetTopTracks};
+use crate::api::track::TrackSearch;
+use crate::api::user::{
+    UserGetFriends, UserGetLovedTracks, UserGetPersonalTags, UserGetRecentTracks, UserGetTopAlbums,
+    UserGetTopArtists, UserGetTopTracks,
+};
+use crate::{
+    APIResponse, AlbumMatch, AlbumSearchResponse, ArtistGetTopAlbumsResponse,
+    ArtistGetTopTracksResponse, ArtistMatch, ArtistSearchResponse, ArtistTopAlbum, ArtistTopTrack,
+    Error, PageInfo, RecentTrack, Result, TrackMatch, TrackSearchResponse,
+    UserGetRecentTracksResponse,
+};
+
+/// The number of pages [`Paginated::all_pages`] fetches at the same time.
+pub const DEFAULT_PAGE_CONCURRENCY: usize = 4;
+
+/// A request builder for a method that returns its results one page at a time.
+///
+/// The first page is fetched on its own to learn the page count, then the remaining pages are
+/// fetched with bounded concurrency and yielded in order.
+pub trait Paginated: Clone + Sized {
+    /// The response to a single page.
+    type Response;
+    /// A single result listed on a page.
+    type Item;
+
+    /// The page this request starts from.
+    fn start_page(&self) -> u32;
+
+    /// The same request, for another page.
+    fn with_page(self, page: u32) -> Self;
+
+    /// Sends the request for the current page.
+    fn send_page(self) -> impl Future<Output = Result<APIResponse<Self::Response>>>;
+
+    /// The pagination metadata of a page, if Last.fm reported it.
+    fn page_info(response: &Self::Response) -> Option<PageInfo>;
+
+    /// The results listed on a page.
+    fn into_items(response: Self::Response) -> Vec<Self::Item>;
+
+    /// Streams every page from [`start_page`](Paginated::start_page) to the last one.
+    fn all_pages(self) -> impl Stream<Item = Result<Self::Response>> {
+        self.all_pages_with_concurrency(DEFAULT_PAGE_CONCURRENCY)
+    }
+
+    /// Like [`all_pages`](Paginated::all_pages), fetching up to `concurrency` pages at a time.
+    fn all_pages_with_concurrency(
+        self,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<Self::Response>> {
+        pages(self, concurrency, None)
+    }
+
+    /// Streams the results of every page, in order.
+    fn all_items(self) -> impl Stream<Item = Result<Self::Item>> {
+        self.all_pages()
+            .map_ok(|response| stream::iter(Self::into_items(response).into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Streams the first `n` results across pages, fetching only the pages needed.
+    fn take_items(self, n: usize) -> impl Stream<Item = Result<Self::Item>> {
+        pages(self, DEFAULT_PAGE_CONCURRENCY, Some(n))
+            .map_ok(|response| stream::iter(Self::into_items(response).into_iter().map(Ok)))
+            .try_flatten()
+            .take(n)
+    }
+}
+
+fn pages<P: Paginated>(
+    request: P,
+    concurrency: usize,
+    max_items: Option<usize>,
+) -> impl Stream<Item = Result<P::Response>> {
+    let start = request.start_page();
+
+    stream::once(async move {
+        let first = success(request.clone().send_page().await?)?;
+        let page_info = P::page_info(&first).unwrap_or_default();
+
+        let mut last = page_info.total_pages.max(start as u64);
+        if let Some(n) = max_items {
+            let per_page = page_info.per_page.max(1);
+            last = last.min(start as u64 + (n as u64).div_ceil(per_page).max(1) - 1);
+        }
+
+        let rest = stream::iter(start as u64 + 1..=last)
+            .map(move |page| {
+                let request = request.clone().with_page(page as u32);
+                async move { success(request.send_page().await?) }
+            })
+            .buffered(concurrency.max(1));
+
+        Ok::<_, Error>(stream::once(future::ready(Ok(first))).chain(rest))
+    })
+    .try_flatten()
+}
+
+fn success<T>(response: APIResponse<T>) -> Result<T> {
+    match response {
+        APIResponse::Success(response) => Ok(response),
+        APIResponse::Error(err) => Err(Error::ApiError(err)),
+    }
+}
+
+/// The results of an untyped response, found under the first of `paths` that exists,
+/// e.g. `["results", "albummatches", "album"]`.
+fn value_items(response: Value, paths: &[&[&str]]) -> Vec<Value> {
+    let found = paths.iter().find_map(|path| {
+        path.iter()
+            .try_fold(&response, |value, key| value.get(key))
+            .cloned()
+    });
+
+    match found {
+        Some(Value::Array(items)) => items,
+        Some(Value::Object(item)) => vec![Value::Object(item)],
+        _ => Vec::new(),
+    }
+}
+
+macro_rules! impl_paginated {
+    ($($builder:ident => [$([$($key:literal),+]),+]),* $(,)?) => {
+        $(impl<'a> Paginated for $builder<'a> {
+            type Response = Value;
+            type Item = Value;
+
+            fn start_page(&self) -> u32 {
+                self.page.unwrap_or(1)
+            }
+
+            fn with_page(mut self, page: u32) -> Self {
+                self.page = Some(page);
+                self
+            }
+
+            fn send_page(self) -> impl Future<Output = Result<APIResponse<Value>>> {
+                self.send()
+            }
+
+            fn page_info(response: &Value) -> Option<PageInfo> {
+                PageInfo::from_value(response)
+            }
+
+            fn into_items(response: Value) -> Vec<Value> {
+                value_items(response, &[$(&[$($key),+]),+])
+            }
+        })*
+    };
+}
+
+impl_paginated!(
+    ChartGetTopArtists => [["artists", "artist"]],
+    ChartGetTopTags => [["tags", "tag"]],
+    ChartGetTopTracks => [["tracks", "track"]],
+    GeoGetTopArtists => [["topartists", "artist"]],
+    GeoGetTopTracks => [["tracks", "track"]],
+    LibraryGetArtists => [["artists", "artist"]],
+    TagGetTopAlbums => [["albums", "album"]],
+    TagGetTopArtists => [["topartists", "artist"]],
+    TagGetTopTracks => [["tracks", "track"]],
+    UserGetFriends => [["friends", "user"]],
+    UserGetLovedTracks => [["lovedtracks", "track"]],
+    UserGetPersonalTags => [
+        ["taggings", "artists", "artist"],
+        ["taggings", "albums", "album"],
+        ["taggings", "tracks", "track"]
+    ],
+    UserGetTopAlbums => [["topalbums", "album"]],
+    UserGetTopArtists => [["topartists", "artist"]],
+    UserGetTopTracks => [["toptracks", "track"]],
+);
+
+macro_rules! impl_typed_paginated {
+    ($(
+        $builder:ident => $response:ident {
+            items: |$items:ident| $into_items:expr => $item:ident,
+            page_info: |$info:ident| $page_info:expr $(,)?
+        }
+    ),* $(,)?) => {
+        $(impl<'a> Paginated for $builder<'a> {
+            type Response = $response;
+            type Item = $item;
+
+            fn start_page(&self) -> u32 {
+                self.page.unwrap_or(1)
+            }
+
+            fn with_page(mut self, page: u32) -> Self {
+                self.page = Some(page);
+                self
+            }
+
+            fn send_page(self) -> impl Future<Output = Result<APIResponse<$response>>> {
+                self.send()
+            }
+
+            fn page_info($info: &$response) -> Option<PageInfo> {
+                Some($page_info)
+            }
+
+            fn into_items($items: $response) -> Vec<$item> {
+                $into_items
+            }
+        })*
+    };
+}
+
+// Recent tracks are walked as-is here; `UserGetRecentTracks::into_stream` also skips the
+// repeated now-playing entry and ignores tracks scrobbled while paging.
+impl_typed_paginated!(
+    AlbumSearch => AlbumSearchResponse {
+        items: |response| response.results.matches => AlbumMatch,
+        page_info: |response| response.results.page_info(),
+    },
+    ArtistGetTopAlbums => ArtistGetTopAlbumsResponse {
+        items: |response| response.topalbums.album => ArtistTopAlbum,
+        page_info: |response| response.topalbums.attr.page_info,
+    },
+    ArtistGetTopTracks => ArtistGetTopTracksResponse {
+        items: |response| response.toptracks.track => ArtistTopTrack,
+        page_info: |response| response.toptracks.attr.page_info,
+    },
+    ArtistSearch => ArtistSearchResponse {
+        items: |response| response.results.matches => ArtistMatch,
+        page_info: |response| response.results.page_info(),
+    },
+    TrackSearch => TrackSearchResponse {
+        items: |response| response.results.matches => TrackMatch,
+        page_info: |response| response.results.page_info(),
+    },
+    UserGetRecentTracks => UserGetRecentTracksResponse {
+        items: |response| response.recenttracks.tracks => RecentTrack,
+        page_info: |response| response.recenttracks.page_info,
+    },
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::lastfm_with;
+    use crate::{LastfmMethod, MockTransport, Transport, TransportFuture, TransportResponse};
+    use reqwest::Method;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Serves `chart.getTopArtists` with `total` artists, numbered from 0.
+    #[derive(Debug, Default)]
+    struct Charts {
+        total: u64,
+        pages: Mutex<Vec<u64>>,
+    }
+
+    impl Transport for Charts {
+        fn send<'a>(
+            &'a self,
+            _url: &'a str,
+            _http_method: Method,
+            params: &'a HashMap<String, String>,
+        ) -> TransportFuture<'a> {
+            let number = |key: &str, default: u64| {
+                params
+                    .get(key)
+                    .map_or(default, |value| value.parse().unwrap())
+            };
+            let page = number("page", 1);
+            let limit = number("limit", 50);
+            self.pages.lock().unwrap().push(page);
+
+            let artists: Vec<Value> = ((page - 1) * limit..(page * limit).min(self.total))
+                .map(|i| json!({ "name": format!("Artist {}", i) }))
+                .collect();
+            let body = json!({
+                "artists": {
+                    "artist": artists,
+                    "@attr": {
+                        "page": page.to_string(),
+                        "perPage": limit.to_string(),
+                        "totalPages": self.total.div_ceil(limit).to_string(),
+                        "total": self.total.to_string(),
+                    }
+                }
+            });
+
+            Box::pin(async move {
+                Ok(TransportResponse {
+                    status: 200,
+                    body: body.to_string(),
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_pages_in_order() {
+        let transport = Arc::new(Charts {
+            total: 23,
+            ..Default::default()
+        });
+        let lastfm = lastfm_with(transport.clone());
+
+        let pages: Vec<Value> = lastfm
+            .chart()
+            .get_top_artists()
+            .limit(5)
+            .all_pages_with_concurrency(3)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 5);
+        let names: Vec<Value> = pages
+            .into_iter()
+            .flat_map(ChartGetTopArtists::into_items)
+            .map(|artist| artist["name"].clone())
+            .collect();
+        assert_eq!(names.len(), 23);
+        assert_eq!(names[0], "Artist 0");
+        assert_eq!(names[22], "Artist 22");
+
+        let mut fetched = transport.pages.lock().unwrap().clone();
+        fetched.sort();
+        assert_eq!(fetched, [1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_take_items_fetches_only_needed_pages() {
+        let transport = Arc::new(Charts {
+            total: 100,
+            ..Default::default()
+        });
+        let lastfm = lastfm_with(transport.clone());
+
+        let artists: Vec<Value> = lastfm
+            .chart()
+            .get_top_artists()
+            .limit(10)
+            .page(2)
+            .take_items(15)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(artists.len(), 15);
+        assert_eq!(artists[0]["name"], "Artist 10");
+        assert_eq!(artists[14]["name"], "Artist 24");
+        assert_eq!(transport.pages.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_stream() {
+        let transport = Arc::new(MockTransport::new().respond(
+            LastfmMethod::ArtistSearch,
+            json!({
+                "results": {
+                    "opensearch:totalResults": "4",
+                    "opensearch:startIndex": "0",
+                    "opensearch:itemsPerPage": "2",
+                    "artistmatches": {
+                        "artist": [
+                            { "name": "Cher", "listeners": "1531145", "url": "" },
+                            { "name": "Cheryl", "listeners": "300000", "url": "" }
+                        ]
+                    },
+                    "@attr": { "for": "cher" }
+                }
+            }),
+        ));
+        let lastfm = lastfm_with(transport.clone());
+
+        let artists: Vec<ArtistMatch> = lastfm
+            .artist()
+            .search()
+            .artist("cher")
+            .limit(2)
+            .all_items()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(artists.len(), 4);
+        assert_eq!(artists[0].listeners, 1_531_145);
+        let pages: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|request| request.get("page").cloned().unwrap_or_default())
+            .collect();
+        assert_eq!(pages, ["", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_api_error_ends_stream() {
+        let transport = Arc::new(MockTransport::new());
+        let lastfm = lastfm_with(transport);
+
+        let result: Result<Vec<Value>> = lastfm
+            .tag()
+            .get_top_albums()
+            .tag("disco")
+            .all_pages()
+            .try_collect()
+            .await;
+
+        assert!(matches!(result, Err(Error::ApiError(err)) if err.error == 3));
+    }
+}
@@ -29,37 +29,37 @@ impl<'a> Tag<'a> {
     }
 
     /// Creates a request to get info for the tag.
-    pub fn get_info(&mut self) -> TagGetInfo<'_> {
+    pub fn get_info(&mut self) -> TagGetInfo<'a> {
         TagGetInfo::new(self.lastfm)
     }
 
     /// Creates a request to get similar tags.
-    pub fn get_similar(&mut self) -> TagGetSimilar<'_> {
+    pub fn get_similar(&mut self) -> TagGetSimilar<'a> {
         TagGetSimilar::new(self.lastfm)
     }
 
     /// Creates a request to get top albums for the tag.
-    pub fn get_top_albums(&mut self) -> TagGetTopAlbums<'_> {
+    pub fn get_top_albums(&mut self) -> TagGetTopAlbums<'a> {
         TagGetTopAlbums::new(self.lastfm)
     }
 
     /// Creates a request to get top artists for the tag.
-    pub fn get_top_artists(&mut self) -> TagGetTopArtists<'_> {
+    pub fn get_top_artists(&mut self) -> TagGetTopArtists<'a> {
         TagGetTopArtists::new(self.lastfm)
     }
 
     /// Creates a request to get top tags for the tag.
-    pub fn get_top_tags(&mut self) -> TagGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> TagGetTopTags<'a> {
         TagGetTopTags::new(self.lastfm)
     }
 
     /// Creates a request to get top tracks for the tag.
-    pub fn get_top_tracks(&mut self) -> TagGetTopTracks<'_> {
+    pub fn get_top_tracks(&mut self) -> TagGetTopTracks<'a> {
         TagGetTopTracks::new(self.lastfm)
     }
 
     /// Creates a request to get the weekly chart list for the tag.
-    pub fn get_weekly_chart_list(&mut self) -> TagGetWeeklyChartList<'_> {
+    pub fn get_weekly_chart_list(&mut self) -> TagGetWeeklyChartList<'a> {
         TagGetWeeklyChartList::new(self.lastfm)
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -34,7 +34,7 @@ impl<'a> TagGetInfo<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        // TODO: check if tag is provided.
+        validation::required("tag", &self.tag)?;
         Ok(())
     }
 
@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -27,6 +27,7 @@ impl<'a> TagGetWeeklyChartList<'a> {
     }
 
     fn validate(&self) -> Result<()> {
+        validation::required("tag", &self.tag)?;
         Ok(())
     }
 
@@ -7,6 +7,7 @@ mod get_top_tags;
 mod love;
 mod remove_tag;
 mod scrobble;
+mod scrobble_batch;
 mod search;
 mod unlove;
 mod update_now_playing;
@@ -22,6 +23,9 @@ pub use get_top_tags::TrackGetTopTags;
 pub use love::TrackLove;
 pub use remove_tag::TrackRemoveTag;
 pub use scrobble::TrackScrobble;
+pub use scrobble_batch::{
+    BatchResult, ScrobbleEntry, ScrobbleResult, TrackScrobbleBatch, MAX_SCROBBLES_PER_REQUEST,
+};
 pub use search::TrackSearch;
 pub use unlove::TrackUnlove;
 pub use update_now_playing::TrackUpdateNowPlaying;
@@ -39,62 +43,67 @@ impl<'a> Track<'a> {
     }
 
     /// Creates a request to get info for the track.
-    pub fn get_info(&mut self) -> TrackGetInfo<'_> {
+    pub fn get_info(&mut self) -> TrackGetInfo<'a> {
         TrackGetInfo::new(self.lastfm)
     }
 
     /// Creates a request to get similar tracks.
-    pub fn get_similar(&mut self) -> TrackGetSimilar<'_> {
+    pub fn get_similar(&mut self) -> TrackGetSimilar<'a> {
         TrackGetSimilar::new(self.lastfm)
     }
 
     /// Creates a request to get tags for the track.
-    pub fn get_tags(&mut self) -> TrackGetTags<'_> {
+    pub fn get_tags(&mut self) -> TrackGetTags<'a> {
         TrackGetTags::new(self.lastfm)
     }
 
     /// Creates a request to get top tags for the track.
-    pub fn get_top_tags(&mut self) -> TrackGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> TrackGetTopTags<'a> {
         TrackGetTopTags::new(self.lastfm)
     }
 
     /// Creates a request to love the track.
-    pub fn love(&mut self) -> TrackLove<'_> {
+    pub fn love(&mut self) -> TrackLove<'a> {
         TrackLove::new(self.lastfm)
     }
 
     /// Creates a request to unlove the track.
-    pub fn unlove(&mut self) -> TrackUnlove<'_> {
+    pub fn unlove(&mut self) -> TrackUnlove<'a> {
         TrackUnlove::new(self.lastfm)
     }
 
     /// Creates a request to add tags to the track.
-    pub fn add_tags(&mut self) -> TrackAddTags<'_> {
+    pub fn add_tags(&mut self) -> TrackAddTags<'a> {
         TrackAddTags::new(self.lastfm)
     }
 
     /// Creates a request to remove a tag from the track.
-    pub fn remove_tag(&mut self) -> TrackRemoveTag<'_> {
+    pub fn remove_tag(&mut self) -> TrackRemoveTag<'a> {
         TrackRemoveTag::new(self.lastfm)
     }
 
     /// Creates a request to scrobble the track.
-    pub fn scrobble(&mut self) -> TrackScrobble<'_> {
+    pub fn scrobble(&mut self) -> TrackScrobble<'a> {
         TrackScrobble::new(self.lastfm)
     }
 
+    /// Creates a request to scrobble several tracks at once.
+    pub fn scrobble_batch(&mut self) -> TrackScrobbleBatch<'a> {
+        TrackScrobbleBatch::new(self.lastfm)
+    }
+
     /// Creates a request to update now playing for the track.
-    pub fn update_now_playing(&mut self) -> TrackUpdateNowPlaying<'_> {
+    pub fn update_now_playing(&mut self) -> TrackUpdateNowPlaying<'a> {
         TrackUpdateNowPlaying::new(self.lastfm)
     }
 
     /// Creates a request to get a correction from the track.
-    pub fn get_correction(&mut self) -> TrackGetCorrection<'_> {
+    pub fn get_correction(&mut self) -> TrackGetCorrection<'a> {
         TrackGetCorrection::new(self.lastfm)
     }
 
     /// Creates a request to search for a track.
-    pub fn search(&mut self) -> TrackSearch<'_> {
+    pub fn search(&mut self) -> TrackSearch<'a> {
         TrackSearch::new(self.lastfm)
     }
 }
@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result, TrackGetInfoResponse,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct TrackGetInfo<'a> {
@@ -55,15 +54,17 @@ impl<'a> TrackGetInfo<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (
+                &["artist", "track"],
+                validation::present(&self.artist) && validation::present(&self.track),
+            ),
+        ])?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<TrackGetInfoResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
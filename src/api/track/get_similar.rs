@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result, TrackGetSimilarResponse,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct TrackGetSimilar<'a> {
@@ -12,7 +11,7 @@ pub struct TrackGetSimilar<'a> {
     pub track: Option<String>,
     pub mbid: Option<String>,
     pub autocorrect: Option<bool>,
-    pub limit: Option<i64>,
+    pub limit: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -44,7 +43,7 @@ impl<'a> TrackGetSimilar<'a> {
         self
     }
 
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
@@ -55,15 +54,18 @@ impl<'a> TrackGetSimilar<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (
+                &["artist", "track"],
+                validation::present(&self.artist) && validation::present(&self.track),
+            ),
+        ])?;
+        validation::limit(&self.limit)?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<TrackGetSimilarResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
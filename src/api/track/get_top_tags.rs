@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -48,11 +48,13 @@ impl<'a> TrackGetTopTags<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.mbid.is_none() && (self.artist.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::any_of(&[
+            (&["mbid"], validation::present(&self.mbid)),
+            (
+                &["artist", "track"],
+                validation::present(&self.artist) && validation::present(&self.track),
+            ),
+        ])?;
         Ok(())
     }
 
@@ -62,8 +64,8 @@ impl<'a> TrackGetTopTags<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.expect("The artist name is required!"))
-            .add("track", self.track.expect("The track name is required!"))
+            .add_optional("artist", self.artist)
+            .add_optional("track", self.track)
             .add_optional("mbid", self.mbid)
             .add_optional("autocorrect", self.autocorrect.map(|b| b.to_string()));
 
@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -41,27 +41,10 @@ impl<'a> TrackRemoveTag<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() {
-            return Err(Error::Generic("Field 'artist' is required.".to_string()));
-        }
-
-        if self.tag.is_none() {
-            return Err(Error::Generic("Field 'tag' is required.".to_string()));
-        }
-
-        let tag_count = self
-            .tag
-            .as_ref()
-            .unwrap()
-            .split(',')
-            .collect::<Vec<_>>()
-            .len();
-        if tag_count > 1 {
-            return Err(Error::Generic(
-                "Only 1 tag is allowed to remove at a time".to_string(),
-            ));
-        }
-
+        validation::required("artist", &self.artist)?;
+        validation::required("track", &self.track)?;
+        validation::required("tag", &self.tag)?;
+        validation::max_items("tag", &self.tag, 1)?;
         Ok(())
     }
 
@@ -71,9 +54,9 @@ impl<'a> TrackRemoveTag<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.unwrap())
-            .add("track", self.track.unwrap())
-            .add("tag", self.tag.unwrap());
+            .add_optional("artist", self.artist)
+            .add_optional("track", self.track)
+            .add_optional("tag", self.tag);
 
         let mut params = builder.build();
 
@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result, TrackScrobbleResponse,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct TrackScrobble<'a> {
@@ -97,15 +96,14 @@ impl<'a> TrackScrobble<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() || self.track.is_none() || self.timestamp.is_none() {
-            return Err(Error::Generic(
-                "'artist', 'track', and 'timestamp' are required.".to_string(),
-            ));
-        }
+        validation::required("artist", &self.artist)?;
+        validation::required("track", &self.track)?;
+        validation::required_value("timestamp", &self.timestamp)?;
+        validation::range("chosenByUser", &self.chosen_by_user, 0, Some(1))?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<TrackScrobbleResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
@@ -0,0 +1,342 @@
+use crate::{
+    api::{LastfmMethod, ParameterBuilder},
+    APIResponse, Error, Lastfm, Result, Scrobble, TrackScrobbleResponse, ValidationError,
+};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of scrobbles Last.fm accepts in a single `track.scrobble` call.
+pub const MAX_SCROBBLES_PER_REQUEST: usize = 50;
+
+/// A single track to be scrobbled as part of a batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScrobbleEntry {
+    pub artist: String,
+    pub track: String,
+    pub timestamp: u64,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub context: Option<String>,
+    pub stream_id: Option<String>,
+    pub chosen_by_user: Option<u8>,
+    pub track_number: Option<u8>,
+    pub mbid: Option<String>,
+    pub duration: Option<u64>,
+}
+
+impl ScrobbleEntry {
+    /// Creates an entry with the fields Last.fm requires for every scrobble.
+    pub fn new<A, T>(artist: A, track: T, timestamp: u64) -> Self
+    where
+        A: Into<String>,
+        T: Into<String>,
+    {
+        ScrobbleEntry {
+            artist: artist.into(),
+            track: track.into(),
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    pub fn album(mut self, album: &str) -> Self {
+        self.album = Some(album.to_string());
+        self
+    }
+
+    pub fn album_artist(mut self, album_artist: &str) -> Self {
+        self.album_artist = Some(album_artist.to_string());
+        self
+    }
+
+    pub fn context(mut self, context: &str) -> Self {
+        self.context = Some(context.to_string());
+        self
+    }
+
+    pub fn stream_id(mut self, stream_id: &str) -> Self {
+        self.stream_id = Some(stream_id.to_string());
+        self
+    }
+
+    pub fn chosen_by_user(mut self, chosen_by_user: u8) -> Self {
+        self.chosen_by_user = Some(chosen_by_user);
+        self
+    }
+
+    pub fn track_number(mut self, track_number: u8) -> Self {
+        self.track_number = Some(track_number);
+        self
+    }
+
+    pub fn mbid(mut self, mbid: &str) -> Self {
+        self.mbid = Some(mbid.to_string());
+        self
+    }
+
+    /// The length of the track in seconds.
+    pub fn duration(mut self, duration: u64) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Checks the fields Last.fm requires for every scrobble.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.artist.trim().is_empty() {
+            return Err(ValidationError::Missing("artist").into());
+        }
+        if self.track.trim().is_empty() {
+            return Err(ValidationError::Missing("track").into());
+        }
+        Ok(())
+    }
+
+    /// Adds this entry's fields to the builder using the `name[index]` array notation.
+    fn add_to(self, builder: ParameterBuilder, index: usize) -> ParameterBuilder {
+        let key = |name: &str| format!("{}[{}]", name, index);
+
+        builder
+            .add(&key("artist"), self.artist)
+            .add(&key("track"), self.track)
+            .add(&key("timestamp"), self.timestamp.to_string())
+            .add_optional(&key("album"), self.album)
+            .add_optional(&key("albumArtist"), self.album_artist)
+            .add_optional(&key("context"), self.context)
+            .add_optional(&key("streamId"), self.stream_id)
+            .add_optional(
+                &key("chosenByUser"),
+                self.chosen_by_user.map(|b| b.to_string()),
+            )
+            .add_optional(
+                &key("trackNumber"),
+                self.track_number.map(|b| b.to_string()),
+            )
+            .add_optional(&key("mbid"), self.mbid)
+            .add_optional(&key("duration"), self.duration.map(|b| b.to_string()))
+    }
+}
+
+/// The outcome of a single entry in a batch scrobble.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrobbleResult {
+    pub entry: ScrobbleEntry,
+    pub accepted: bool,
+    pub scrobble: Scrobble,
+}
+
+/// The outcome of [`TrackScrobbleBatch::send`].
+///
+/// Chunks answered before a failure have already been recorded by Last.fm, so their results
+/// are kept alongside the error and must not be submitted again.
+#[derive(Debug, Default)]
+pub struct BatchResult {
+    /// One result per submitted entry, in the order the entries were added. Last.fm may still
+    /// have ignored some of them; check [`ScrobbleResult::accepted`].
+    pub submitted: Vec<ScrobbleResult>,
+    /// The index of the first entry that was not submitted, if a chunk failed.
+    pub failed_from: Option<usize>,
+    /// Why the chunk starting at `failed_from` failed.
+    pub error: Option<Error>,
+}
+
+impl BatchResult {
+    /// Whether every entry was submitted.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// The results of the whole batch, or the error of the chunk that failed.
+    pub fn into_result(self) -> Result<Vec<ScrobbleResult>> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.submitted),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackScrobbleBatch<'a> {
+    lastfm: &'a Lastfm,
+    pub entries: Vec<ScrobbleEntry>,
+    method: LastfmMethod,
+}
+
+impl<'a> TrackScrobbleBatch<'a> {
+    pub(crate) fn new(lastfm: &'a Lastfm) -> Self {
+        TrackScrobbleBatch {
+            lastfm,
+            entries: Vec::new(),
+            method: LastfmMethod::TrackScrobble,
+        }
+    }
+
+    /// Adds a single entry to the batch.
+    pub fn entry(mut self, entry: ScrobbleEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Adds several entries to the batch.
+    pub fn entries<I>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = ScrobbleEntry>,
+    {
+        self.entries.extend(entries);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.entries.is_empty() {
+            return Err(ValidationError::Missing("entries").into());
+        }
+
+        self.entries.iter().try_for_each(ScrobbleEntry::validate)
+    }
+
+    /// Sends the entries in chunks of [`MAX_SCROBBLES_PER_REQUEST`], one signed request per chunk.
+    ///
+    /// Results are returned in the order the entries were added. If a chunk fails, the
+    /// remaining chunks are not sent and the results of the chunks already answered are
+    /// returned together with the error. An `Err` is only returned when the batch is invalid
+    /// and nothing was sent.
+    pub async fn send(self) -> Result<BatchResult> {
+        self.validate()?;
+
+        let mut result = BatchResult {
+            submitted: Vec::with_capacity(self.entries.len()),
+            ..Default::default()
+        };
+
+        for (number, chunk) in self.entries.chunks(MAX_SCROBBLES_PER_REQUEST).enumerate() {
+            match self.send_chunk(chunk).await {
+                Ok(results) => result.submitted.extend(results),
+                Err(err) => {
+                    result.failed_from = Some(number * MAX_SCROBBLES_PER_REQUEST);
+                    result.error = Some(err);
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn send_chunk(&self, chunk: &[ScrobbleEntry]) -> Result<Vec<ScrobbleResult>> {
+        let mut builder = ParameterBuilder::new();
+        for (index, entry) in chunk.iter().cloned().enumerate() {
+            builder = entry.add_to(builder, index);
+        }
+
+        let mut params = builder.build();
+
+        let response: APIResponse<TrackScrobbleResponse> = self
+            .lastfm
+            .send_request(self.method.clone(), &mut params, Method::POST)
+            .await?;
+
+        let scrobbles = match response {
+            APIResponse::Success(response) => response.scrobbles.scrobble,
+            APIResponse::Error(err) => return Err(Error::ApiError(err)),
+        };
+
+        if scrobbles.len() != chunk.len() {
+            return Err(Error::ScrobbleCountMismatch {
+                sent: chunk.len(),
+                echoed: scrobbles.len(),
+            });
+        }
+
+        // Last.fm echoes the scrobbles back in the order they were submitted.
+        Ok(chunk
+            .iter()
+            .cloned()
+            .zip(scrobbles)
+            .map(|(entry, scrobble)| ScrobbleResult {
+                entry,
+                accepted: !scrobble.ignored_message.code.is_ignored(),
+                scrobble,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::{lastfm_with, scrobble_echo};
+    use crate::MockTransport;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_failed_chunk_keeps_earlier_results() {
+        let entries: Vec<_> = (0..MAX_SCROBBLES_PER_REQUEST as u64 + 1)
+            .map(|i| ScrobbleEntry::new("Cher", format!("Track {}", i), 1_700_000_000 + i))
+            .collect();
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond_once(
+                    LastfmMethod::TrackScrobble,
+                    scrobble_echo(&entries[..MAX_SCROBBLES_PER_REQUEST]),
+                )
+                .respond_once(
+                    LastfmMethod::TrackScrobble,
+                    json!({ "error": 16, "message": "Temporary error" }),
+                ),
+        );
+        let lastfm = lastfm_with(transport.clone());
+
+        let batch = lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!batch.is_complete());
+        assert_eq!(batch.submitted.len(), MAX_SCROBBLES_PER_REQUEST);
+        assert_eq!(batch.failed_from, Some(MAX_SCROBBLES_PER_REQUEST));
+        assert!(matches!(batch.error, Some(Error::ApiError(ref err)) if err.error == 16));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_short_echo_is_an_error() {
+        let entries = vec![
+            ScrobbleEntry::new("Cher", "Believe", 1_700_000_000),
+            ScrobbleEntry::new("Cher", "Strong Enough", 1_700_000_100),
+        ];
+        let transport = Arc::new(
+            MockTransport::new().respond(LastfmMethod::TrackScrobble, scrobble_echo(&entries[..1])),
+        );
+        let lastfm = lastfm_with(transport);
+
+        let batch = lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(batch.submitted.is_empty());
+        assert_eq!(batch.failed_from, Some(0));
+        assert!(matches!(
+            batch.error,
+            Some(Error::ScrobbleCountMismatch { sent: 2, echoed: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_entry_params_use_array_notation() {
+        let entry = ScrobbleEntry::new("Artist", "Track", 1700000000).album("Album");
+        let params = entry.add_to(ParameterBuilder::new(), 3).build();
+
+        assert_eq!(params.get("artist[3]").unwrap(), "Artist");
+        assert_eq!(params.get("track[3]").unwrap(), "Track");
+        assert_eq!(params.get("timestamp[3]").unwrap(), "1700000000");
+        assert_eq!(params.get("album[3]").unwrap(), "Album");
+        assert!(!params.contains_key("mbid[3]"));
+    }
+}
@@ -1,17 +1,16 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result, TrackSearchResponse,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct TrackSearch<'a> {
     lastfm: &'a Lastfm,
     pub artist: Option<String>,
     pub track: Option<String>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -37,31 +36,30 @@ impl<'a> TrackSearch<'a> {
         self
     }
 
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() || self.artist.as_ref().unwrap().is_empty() {
-            return Err(Error::Generic("The artist name is required.".to_string()));
-        }
-
+        validation::required("track", &self.track)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<TrackSearchResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("track", self.track.expect("The track name is required!"))
+            .add_optional("track", self.track)
             .add_optional("artist", self.artist)
             .add_optional("limit", self.limit.map(|b| b.to_string()))
             .add_optional("page", self.page.map(|b| b.to_string()));
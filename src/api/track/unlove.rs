@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -40,14 +40,8 @@ impl<'a> TrackUnlove<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.artist.is_none() {
-            return Err(Error::Generic("Field 'artist' is required.".to_string()));
-        }
-
-        if self.track.is_none() {
-            return Err(Error::Generic("Field 'track' is required.".to_string()));
-        }
-
+        validation::required("artist", &self.artist)?;
+        validation::required("track", &self.track)?;
         Ok(())
     }
 
@@ -56,8 +50,8 @@ impl<'a> TrackUnlove<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("artist", self.artist.expect("The artist name is required!"))
-            .add("track", self.track.expect("The track name is required!"));
+            .add_optional("artist", self.artist)
+            .add_optional("track", self.track);
 
         let mut params = builder.build();
 
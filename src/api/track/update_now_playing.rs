@@ -1,9 +1,8 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result, TrackUpdateNowPlayingResponse,
 };
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct TrackUpdateNowPlaying<'a> {
@@ -75,10 +74,14 @@ impl<'a> TrackUpdateNowPlaying<'a> {
     }
 
     fn validate(&self) -> Result<()> {
+        validation::required("artist", &self.artist)?;
+        validation::required("track", &self.track)?;
+        validation::range("trackNumber", &self.track_number, 1, None)?;
+        validation::range("duration", &self.duration, 1, None)?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<TrackUpdateNowPlayingResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
@@ -1,3 +1,4 @@
+mod chart_range;
 mod get_friends;
 mod get_info;
 mod get_loved_track;
@@ -11,9 +12,11 @@ mod get_weekly_album_chart;
 mod get_weekly_artist_chart;
 mod get_weekly_chart_list;
 mod get_weekly_track_chart;
+mod period;
 
 use crate::Lastfm;
 
+pub use chart_range::MAX_RANGE_WEEKS;
 pub use get_friends::UserGetFriends;
 pub use get_info::UserGetInfo;
 pub use get_loved_track::UserGetLovedTracks;
@@ -27,6 +30,7 @@ pub use get_weekly_album_chart::UserGetWeeklyAlbumChart;
 pub use get_weekly_artist_chart::UserGetWeeklyArtistChart;
 pub use get_weekly_chart_list::UserGetWeeklyChartList;
 pub use get_weekly_track_chart::UserGetWeeklyTrackChart;
+pub use period::Period;
 
 /// Represents user-related operations in the Last.fm API.
 #[derive(Debug)]
@@ -41,67 +45,67 @@ impl<'a> User<'a> {
     }
 
     /// Creates a request to get information about the user.
-    pub fn get_info(&mut self) -> UserGetInfo<'_> {
+    pub fn get_info(&mut self) -> UserGetInfo<'a> {
         UserGetInfo::new(self.lastfm)
     }
 
     /// Creates a request to get the user's friends.
-    pub fn get_friends(&mut self) -> UserGetFriends<'_> {
+    pub fn get_friends(&mut self) -> UserGetFriends<'a> {
         UserGetFriends::new(self.lastfm)
     }
 
     /// Creates a request to get the user's loved tracks.
-    pub fn get_loved_tracks(&mut self) -> UserGetLovedTracks<'_> {
+    pub fn get_loved_tracks(&mut self) -> UserGetLovedTracks<'a> {
         UserGetLovedTracks::new(self.lastfm)
     }
 
     /// Creates a request to get personal tags for the user.
-    pub fn get_personal_tags(&mut self) -> UserGetPersonalTags<'_> {
+    pub fn get_personal_tags(&mut self) -> UserGetPersonalTags<'a> {
         UserGetPersonalTags::new(self.lastfm)
     }
 
     /// Creates a request to get the user's recent tracks.
-    pub fn get_recent_tracks(&mut self) -> UserGetRecentTracks<'_> {
+    pub fn get_recent_tracks(&mut self) -> UserGetRecentTracks<'a> {
         UserGetRecentTracks::new(self.lastfm)
     }
 
     /// Creates a request to get the user's top albums.
-    pub fn get_top_albums(&mut self) -> UserGetTopAlbums<'_> {
+    pub fn get_top_albums(&mut self) -> UserGetTopAlbums<'a> {
         UserGetTopAlbums::new(self.lastfm)
     }
 
     /// Creates a request to get the user's top artists.
-    pub fn get_top_artists(&mut self) -> UserGetTopArtists<'_> {
+    pub fn get_top_artists(&mut self) -> UserGetTopArtists<'a> {
         UserGetTopArtists::new(self.lastfm)
     }
 
     /// Creates a request to get the user's top tags.
-    pub fn get_top_tags(&mut self) -> UserGetTopTags<'_> {
+    pub fn get_top_tags(&mut self) -> UserGetTopTags<'a> {
         UserGetTopTags::new(self.lastfm)
     }
 
     /// Creates a request to get the user's top tracks.
-    pub fn get_top_tracks(&mut self) -> UserGetTopTracks<'_> {
+    pub fn get_top_tracks(&mut self) -> UserGetTopTracks<'a> {
         UserGetTopTracks::new(self.lastfm)
     }
 
     /// Creates a request to get the user's weekly album chart.
-    pub fn get_weekly_album_chart(&mut self) -> UserGetWeeklyAlbumChart<'_> {
+    pub fn get_weekly_album_chart(&mut self) -> UserGetWeeklyAlbumChart<'a> {
         UserGetWeeklyAlbumChart::new(self.lastfm)
     }
 
     /// Creates a request to get the user's weekly artist chart.
-    pub fn get_weekly_artist_chart(&mut self) -> UserGetWeeklyArtistChart<'_> {
+    pub fn get_weekly_artist_chart(&mut self) -> UserGetWeeklyArtistChart<'a> {
         UserGetWeeklyArtistChart::new(self.lastfm)
     }
 
     /// Creates a request to get the user's weekly chart list.
-    pub fn get_weekly_chart_list(&mut self) -> UserGetWeeklyChartList<'_> {
+    pub fn get_weekly_chart_list(&mut self) -> UserGetWeeklyChartList<'a> {
         UserGetWeeklyChartList::new(self.lastfm)
     }
 
     /// Creates a request to get the user's weekly track chart.
-    pub fn get_weekly_track_chart(&mut self) -> UserGetWeeklyTrackChart<'_> {
+    pub fn get_weekly_track_chart(&mut self) -> UserGetWeeklyTrackChart<'a> {
         UserGetWeeklyTrackChart::new(self.lastfm)
     }
 }
@@ -0,0 +1,665 @@
+//! Top charts for arbitrary time ranges, built from the user's weekly charts.
+//!
+//! Last.fm only computes the top-* charts for the fixed [`Period`](super::Period)s. For any
+//! other range the weekly charts overlapping it are fetched and their play counts summed, so
+//! the range is effectively widened to whole chart weeks.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::api::{validation, LastfmMethod, ParameterBuilder, DEFAULT_PAGE_CONCURRENCY};
+use crate::models::de::{one_or_many, u64_from_str};
+use crate::{APIResponse, Lastfm, Result, ValidationError};
+
+/// The longest custom range, in weeks. Each week overlapping the range costs one request.
+pub const MAX_RANGE_WEEKS: u64 = 52;
+
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Chart weeks start on Sunday at 12:00 UTC, this long after the Unix epoch (a Thursday).
+const CHART_WEEK_START: u64 = 3 * 24 * 60 * 60 + 12 * 60 * 60;
+
+/// Adds the `period` and `range` setters to a user top-* builder.
+macro_rules! period_and_range {
+    () => {
+        pub fn period(mut self, period: Period) -> Self {
+            self.period = Some(period);
+            self.range = None;
+            self
+        }
+
+        /// Computes the chart between two Unix timestamps instead of over a fixed period.
+        ///
+        /// Last.fm has no such option, so the chart is aggregated from the user's weekly
+        /// charts that overlap the range, one request per week. The range may span at most
+        /// [`MAX_RANGE_WEEKS`](crate::MAX_RANGE_WEEKS) weeks. The aggregated chart
+        /// is shared by the clones of this request, so walking its pages fetches the weekly
+        /// charts only once.
+        pub fn range(mut self, from: u64, to: u64) -> Self {
+            self.range = Some((from, to));
+            self.period = None;
+            self
+        }
+    };
+}
+pub(super) use period_and_range;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ChartKind {
+    Artist,
+    Album,
+    Track,
+}
+
+impl ChartKind {
+    fn item_key(self) -> &'static str {
+        match self {
+            ChartKind::Artist => "artist",
+            ChartKind::Album => "album",
+            ChartKind::Track => "track",
+        }
+    }
+
+    fn top_key(self) -> &'static str {
+        match self {
+            ChartKind::Artist => "topartists",
+            ChartKind::Album => "topalbums",
+            ChartKind::Track => "toptracks",
+        }
+    }
+
+    fn weekly_key(self) -> &'static str {
+        match self {
+            ChartKind::Artist => "weeklyartistchart",
+            ChartKind::Album => "weeklyalbumchart",
+            ChartKind::Track => "weeklytrackchart",
+        }
+    }
+
+    fn weekly_method(self) -> LastfmMethod {
+        match self {
+            ChartKind::Artist => LastfmMethod::UserGetWeeklyArtistChart,
+            ChartKind::Album => LastfmMethod::UserGetWeeklyAlbumChart,
+            ChartKind::Track => LastfmMethod::UserGetWeeklyTrackChart,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WeeklyChartListResponse {
+    weeklychartlist: WeeklyChartList,
+}
+
+#[derive(Deserialize)]
+struct WeeklyChartList {
+    #[serde(default, deserialize_with = "one_or_many")]
+    chart: Vec<ChartWeek>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct ChartWeek {
+    #[serde(deserialize_with = "u64_from_str")]
+    from: u64,
+    #[serde(deserialize_with = "u64_from_str")]
+    to: u64,
+}
+
+/// An entry of the aggregated chart.
+#[derive(Debug)]
+struct Entry {
+    item: Value,
+    playcount: u64,
+}
+
+/// Groups entries across weekly charts by artist and name, ignoring case and surrounding
+/// whitespace. Within a group, entries with different MusicBrainz IDs are kept apart.
+#[derive(PartialEq, Eq, Hash)]
+struct EntryKey {
+    artist: String,
+    name: String,
+}
+
+/// The aggregated chart of the last range requested through a builder and its clones.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RangeChart {
+    cached: Arc<Mutex<Option<Aggregated>>>,
+}
+
+#[derive(Debug)]
+struct Aggregated {
+    user: String,
+    range: (u64, u64),
+    entries: Arc<Vec<Entry>>,
+}
+
+impl RangeChart {
+    /// The entries of the chart, sorted by play count, aggregated on the first call only.
+    async fn entries(
+        &self,
+        lastfm: &Lastfm,
+        kind: ChartKind,
+        user: &str,
+        range: (u64, u64),
+    ) -> Result<APIResponse<Arc<Vec<Entry>>>> {
+        // Held while aggregating, so pages fetched concurrently wait for the first one.
+        let mut cached = self.cached.lock().await;
+        if let Some(aggregated) = cached
+            .as_ref()
+            .filter(|aggregated| aggregated.user == user && aggregated.range == range)
+        {
+            return Ok(APIResponse::Success(aggregated.entries.clone()));
+        }
+
+        let entries = match aggregate(lastfm, kind, user, range).await? {
+            APIResponse::Success(entries) => Arc::new(entries),
+            APIResponse::Error(err) => return Ok(APIResponse::Error(err)),
+        };
+        *cached = Some(Aggregated {
+            user: user.to_string(),
+            range,
+            entries: entries.clone(),
+        });
+        Ok(APIResponse::Success(entries))
+    }
+}
+
+/// Checks that `range` is ordered and spans at most [`MAX_RANGE_WEEKS`] weeks.
+pub(crate) fn validate(range: Option<(u64, u64)>) -> Result<()> {
+    let Some((from, to)) = range else {
+        return Ok(());
+    };
+
+    validation::time_range(&Some(from), &Some(to))?;
+    let weeks = chart_weeks(from, to);
+    if weeks > MAX_RANGE_WEEKS {
+        return Err(ValidationError::TooMany {
+            field: "range",
+            max: MAX_RANGE_WEEKS as usize,
+            actual: weeks as usize,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The number of chart weeks overlapping `from..to`.
+fn chart_weeks(from: u64, to: u64) -> u64 {
+    // Shifted so that chart weeks start at multiples of `WEEK_SECS`.
+    let shift = WEEK_SECS - CHART_WEEK_START;
+    (to + shift).div_ceil(WEEK_SECS) - (from + shift) / WEEK_SECS
+}
+
+/// Computes the top-* chart of `user` between `from` and `to`, in the shape of the
+/// corresponding `user.getTop*` response.
+pub(crate) async fn top_chart(
+    lastfm: &Lastfm,
+    kind: ChartKind,
+    chart: &RangeChart,
+    user: String,
+    range: (u64, u64),
+    limit: u32,
+    page: u32,
+) -> Result<APIResponse<Value>> {
+    let entries = match chart.entries(lastfm, kind, &user, range).await? {
+        APIResponse::Success(entries) => entries,
+        APIResponse::Error(err) => return Ok(APIResponse::Error(err)),
+    };
+
+    let total = entries.len() as u64;
+    let per_page = u64::from(limit.max(1));
+    let items: Vec<Value> = entries
+        .iter()
+        .enumerate()
+        .skip((u64::from(page.max(1)) - 1).saturating_mul(per_page) as usize)
+        .take(per_page as usize)
+        .map(|(rank, entry)| top_item(kind, entry, rank + 1))
+        .collect();
+
+    Ok(APIResponse::Success(json!({
+        kind.top_key(): {
+            kind.item_key(): items,
+            "@attr": {
+                "user": user,
+                "page": page.max(1).to_string(),
+                "perPage": per_page.to_string(),
+                "totalPages": total.div_ceil(per_page).to_string(),
+                "total": total.to_string(),
+            }
+        }
+    })))
+}
+
+/// Fetches the weekly charts overlapping `(from, to)` and sums their play counts.
+async fn aggregate(
+    lastfm: &Lastfm,
+    kind: ChartKind,
+    user: &str,
+    (from, to): (u64, u64),
+) -> Result<APIResponse<Vec<Entry>>> {
+    let mut params = ParameterBuilder::new()
+        .add("user", user.to_string())
+        .build();
+    let weeks = match lastfm
+        .send_request::<WeeklyChartListResponse>(
+            LastfmMethod::UserGetWeeklyChartList,
+            &mut params,
+            Method::GET,
+        )
+        .await?
+    {
+        APIResponse::Success(response) => response.weeklychartlist.chart,
+        APIResponse::Error(err) => return Ok(APIResponse::Error(err)),
+    };
+
+    let charts: Vec<APIResponse<Value>> = stream::iter(
+        weeks
+            .into_iter()
+            .filter(|week| week.from < to && week.to > from),
+    )
+    .map(|week| {
+        let mut params = ParameterBuilder::new()
+            .add("user", user.to_string())
+            .add("from", week.from.to_string())
+            .add("to", week.to.to_string())
+            .build();
+        async move {
+            lastfm
+                .send_request(kind.weekly_method(), &mut params, Method::GET)
+                .await
+        }
+    })
+    .buffered(DEFAULT_PAGE_CONCURRENCY)
+    .try_collect()
+    .await?;
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut groups: HashMap<EntryKey, Vec<usize>> = HashMap::new();
+    for chart in charts {
+        let mut chart = match chart {
+            APIResponse::Success(chart) => chart,
+            APIResponse::Error(err) => return Ok(APIResponse::Error(err)),
+        };
+        let items = match chart[kind.weekly_key()][kind.item_key()].take() {
+            Value::Array(items) => items,
+            item @ Value::Object(_) => vec![item],
+            _ => Vec::new(),
+        };
+
+        for item in items {
+            let playcount = text(&item["playcount"]).parse().unwrap_or(0);
+            let mbid = text(&item["mbid"]);
+            let group = groups.entry(entry_key(kind, &item)).or_default();
+
+            match matching_entry(&entries, group, &mbid) {
+                Some(position) => {
+                    let entry = &mut entries[position];
+                    entry.playcount += playcount;
+                    if !mbid.is_empty() {
+                        entry.item["mbid"] = json!(mbid);
+                    }
+                }
+                None => {
+                    group.push(entries.len());
+                    entries.push(Entry { item, playcount });
+                }
+            }
+        }
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.playcount));
+
+    Ok(APIResponse::Success(entries))
+}
+
+fn entry_key(kind: ChartKind, item: &Value) -> EntryKey {
+    let artist = match kind {
+        ChartKind::Artist => String::new(),
+        _ => artist_name(&item["artist"]),
+    };
+    EntryKey {
+        artist: artist.trim().to_lowercase(),
+        name: text(&item["name"]).trim().to_lowercase(),
+    }
+}
+
+/// The entry of `group` an item with `mbid` adds to: the one with the same MusicBrainz ID, or
+/// one that has none yet. An item without an ID adds to the first entry of its group.
+fn matching_entry(entries: &[Entry], group: &[usize], mbid: &str) -> Option<usize> {
+    if mbid.is_empty() {
+        return group.first().copied();
+    }
+
+    let mbid_of = |position: usize| text(&entries[position].item["mbid"]);
+    let same = group
+        .iter()
+        .copied()
+        .find(|&position| mbid_of(position) == mbid);
+    same.or_else(|| {
+        group
+            .iter()
+            .copied()
+            .find(|&position| mbid_of(position).is_empty())
+    })
+}
+
+/// Rewrites a weekly chart entry as an entry of the top-* chart.
+fn top_item(kind: ChartKind, entry: &Entry, rank: usize) -> Value {
+    let mut item = entry.item.clone();
+    item["playcount"] = json!(entry.playcount.to_string());
+    item["@attr"] = json!({ "rank": rank.to_string() });
+    if !matches!(kind, ChartKind::Artist) {
+        item["artist"] = json!({
+            "name": artist_name(&item["artist"]),
+            "mbid": text(&item["artist"]["mbid"]),
+        });
+    }
+    item
+}
+
+/// Weekly charts name the artist in `#text`, top-* charts in `name`.
+fn artist_name(artist: &Value) -> String {
+    match artist.get("#text") {
+        Some(name) => text(name),
+        None => text(&artist["name"]),
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::lastfm_with;
+    use crate::{Error, MockTransport, Paginated, Period};
+
+    fn chart_list() -> Value {
+        json!({
+            "weeklychartlist": {
+                "chart": [
+                    { "#text": "", "from": "1000", "to": "2000" },
+                    { "#text": "", "from": "2000", "to": "3000" },
+                    { "#text": "", "from": "3000", "to": "4000" }
+                ],
+                "@attr": { "user": "uppercase_" }
+            }
+        })
+    }
+
+    fn success(response: APIResponse<Value>) -> Value {
+        match response {
+            APIResponse::Success(response) => response,
+            APIResponse::Error(err) => panic!("unexpected API error: {}", err.message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_top_artists_for_custom_range() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(
+                    LastfmMethod::UserGetWeeklyChartList,
+                    json!({
+                        "weeklychartlist": {
+                            "chart": [
+                                { "#text": "", "from": "1000", "to": "2000" },
+                                { "#text": "", "from": "2000", "to": "3000" },
+                                { "#text": "", "from": "3000", "to": "4000" }
+                            ],
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                )
+                .respond(
+                    LastfmMethod::UserGetWeeklyArtistChart,
+                    json!({
+                        "weeklyartistchart": {
+                            "artist": [
+                                { "mbid": "", "playcount": "2", "name": "Cher", "url": "", "@attr": { "rank": "1" } },
+                                { "mbid": "", "playcount": "5", "name": "ABBA", "url": "", "@attr": { "rank": "2" } }
+                            ],
+                            "@attr": { "user": "uppercase_", "from": "1000", "to": "2000" }
+                        }
+                    }),
+                ),
+        );
+        let lastfm = lastfm_with(transport.clone());
+
+        let response = lastfm
+            .user()
+            .get_top_artists()
+            .user("uppercase_")
+            .period(Period::SevenDays)
+            .range(1500, 2500)
+            .send()
+            .await
+            .unwrap();
+
+        let top = match response {
+            APIResponse::Success(top) => top,
+            APIResponse::Error(err) => panic!("unexpected API error: {}", err.message),
+        };
+        let artists = top["topartists"]["artist"].as_array().unwrap();
+        assert_eq!(artists[0]["name"], "ABBA");
+        assert_eq!(artists[0]["playcount"], "10");
+        assert_eq!(artists[0]["@attr"]["rank"], "1");
+        assert_eq!(artists[1]["name"], "Cher");
+        assert_eq!(artists[1]["playcount"], "4");
+        assert_eq!(top["topartists"]["@attr"]["total"], "2");
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|request| !request.contains_key("period")));
+    }
+
+    #[tokio::test]
+    async fn test_top_artists_keyed_by_mbid() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(LastfmMethod::UserGetWeeklyChartList, chart_list())
+                .respond(
+                    LastfmMethod::UserGetWeeklyArtistChart,
+                    json!({
+                        "weeklyartistchart": {
+                            "artist": [
+                                { "mbid": "nirvana-us", "playcount": "3", "name": "Nirvana", "url": "" },
+                                { "mbid": "nirvana-uk", "playcount": "1", "name": "Nirvana", "url": "" }
+                            ],
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                ),
+        );
+        let lastfm = lastfm_with(transport);
+
+        let top = success(
+            lastfm
+                .user()
+                .get_top_artists()
+                .user("uppercase_")
+                .range(1500, 2500)
+                .send()
+                .await
+                .unwrap(),
+        );
+        let artists = top["topartists"]["artist"].as_array().unwrap();
+        assert_eq!(artists.len(), 2);
+        assert_eq!(artists[0]["mbid"], "nirvana-us");
+        assert_eq!(artists[0]["playcount"], "6");
+        assert_eq!(artists[1]["mbid"], "nirvana-uk");
+        assert_eq!(artists[1]["playcount"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_entries_without_mbid_merge_by_name() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(LastfmMethod::UserGetWeeklyChartList, chart_list())
+                .respond_once(
+                    LastfmMethod::UserGetWeeklyArtistChart,
+                    json!({
+                        "weeklyartistchart": {
+                            "artist": { "mbid": "", "playcount": "2", "name": "Cher", "url": "" },
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                )
+                .respond_once(
+                    LastfmMethod::UserGetWeeklyArtistChart,
+                    json!({
+                        "weeklyartistchart": {
+                            "artist": { "mbid": "cher-mbid", "playcount": "3", "name": "cher ", "url": "" },
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                ),
+        );
+        let lastfm = lastfm_with(transport);
+
+        let top = success(
+            lastfm
+                .user()
+                .get_top_artists()
+                .user("uppercase_")
+                .range(1500, 2500)
+                .send()
+                .await
+                .unwrap(),
+        );
+        let artists = top["topartists"]["artist"].as_array().unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0]["name"], "Cher");
+        assert_eq!(artists[0]["mbid"], "cher-mbid");
+        assert_eq!(artists[0]["playcount"], "5");
+    }
+
+    #[tokio::test]
+    async fn test_top_albums_for_custom_range() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(LastfmMethod::UserGetWeeklyChartList, chart_list())
+                .respond_once(
+                    LastfmMethod::UserGetWeeklyAlbumChart,
+                    json!({
+                        "weeklyalbumchart": {
+                            "album": [
+                                { "artist": { "mbid": "", "#text": "Cher" }, "mbid": "", "name": "Greatest Hits", "playcount": "2", "url": "" },
+                                { "artist": { "mbid": "", "#text": "ABBA" }, "mbid": "", "name": "Greatest Hits", "playcount": "3", "url": "" }
+                            ],
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                )
+                .respond_once(
+                    LastfmMethod::UserGetWeeklyAlbumChart,
+                    json!({
+                        "weeklyalbumchart": {
+                            "album": { "artist": { "mbid": "", "#text": "Cher" }, "mbid": "", "name": "Greatest Hits", "playcount": "4", "url": "" },
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                ),
+        );
+        let lastfm = lastfm_with(transport);
+
+        let top = success(
+            lastfm
+                .user()
+                .get_top_albums()
+                .user("uppercase_")
+                .range(1500, 2500)
+                .send()
+                .await
+                .unwrap(),
+        );
+        let albums = top["topalbums"]["album"].as_array().unwrap();
+        assert_eq!(albums.len(), 2);
+        assert_eq!(albums[0]["artist"]["name"], "Cher");
+        assert_eq!(albums[0]["playcount"], "6");
+        assert_eq!(albums[0]["@attr"]["rank"], "1");
+        assert_eq!(albums[1]["artist"]["name"], "ABBA");
+        assert_eq!(albums[1]["playcount"], "3");
+    }
+
+    #[tokio::test]
+    async fn test_top_tracks_pages_aggregate_once() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(LastfmMethod::UserGetWeeklyChartList, chart_list())
+                .respond(
+                    LastfmMethod::UserGetWeeklyTrackChart,
+                    json!({
+                        "weeklytrackchart": {
+                            "track": [
+                                { "artist": { "mbid": "", "#text": "Cher" }, "mbid": "", "name": "Believe", "playcount": "3", "url": "" },
+                                { "artist": { "mbid": "", "#text": "ABBA" }, "mbid": "", "name": "SOS", "playcount": "2", "url": "" },
+                                { "artist": { "mbid": "", "#text": "ABBA" }, "mbid": "", "name": "Waterloo", "playcount": "1", "url": "" }
+                            ],
+                            "@attr": { "user": "uppercase_" }
+                        }
+                    }),
+                ),
+        );
+        let lastfm = lastfm_with(transport.clone());
+
+        let names: Vec<Value> = lastfm
+            .user()
+            .get_top_tracks()
+            .user("uppercase_")
+            .range(1500, 3500)
+            .limit(1)
+            .all_items()
+            .map_ok(|track| track["name"].clone())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(names, ["Believe", "SOS", "Waterloo"]);
+
+        // The chart list and three weeks, fetched for the first page only.
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_range_is_rejected() {
+        let transport = Arc::new(MockTransport::new());
+        let lastfm = lastfm_with(transport.clone());
+
+        let result = lastfm
+            .user()
+            .get_top_artists()
+            .user("uppercase_")
+            .range(0, (MAX_RANGE_WEEKS + 1) * WEEK_SECS)
+            .send()
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::TooMany {
+                field: "range",
+                ..
+            }))
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_range_counts_overlapping_chart_weeks() {
+        let week_start = CHART_WEEK_START + 100 * WEEK_SECS;
+        let weeks = |count: u64| week_start + count * WEEK_SECS;
+
+        assert!(validate(Some((week_start, weeks(MAX_RANGE_WEEKS)))).is_ok());
+        assert!(validate(Some((weeks(1) - 1, weeks(MAX_RANGE_WEEKS + 1) - 1))).is_err());
+        assert_eq!(chart_weeks(week_start + 1, week_start + 2), 1);
+    }
+}
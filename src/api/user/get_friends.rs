@@ -1,5 +1,5 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
+    api::{validation, LastfmMethod, ParameterBuilder},
     APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
@@ -10,8 +10,8 @@ pub struct UserGetFriends<'a> {
     lastfm: &'a Lastfm,
     pub user: Option<String>,
     pub recenttracks: Option<String>,
-    pub limit: Option<String>,
-    pub page: Option<String>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -37,17 +37,20 @@ impl<'a> UserGetFriends<'a> {
         self
     }
 
-    pub fn page(mut self, page: &str) -> Self {
-        self.page = Some(page.to_string());
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
         self
     }
 
-    pub fn limit(mut self, limit: bool) -> Self {
-        self.limit = Some(limit.to_string());
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
         self
     }
 
     fn validate(&self) -> Result<()> {
+        validation::required("user", &self.user)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
@@ -59,8 +62,8 @@ impl<'a> UserGetFriends<'a> {
         builder = builder
             .add_optional("user", self.user)
             .add_optional("recenttracks", self.recenttracks)
-            .add_optional("limit", self.limit)
-            .add_optional("page", self.page);
+            .add_optional("limit", self.limit.map(|l| l.to_string()))
+            .add_optional("page", self.page.map(|p| p.to_string()));
 
         let mut params = builder.build();
 
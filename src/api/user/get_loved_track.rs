@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -9,8 +9,8 @@ use serde_json::Value;
 pub struct UserGetLovedTracks<'a> {
     lastfm: &'a Lastfm,
     pub user: Option<String>,
-    pub limit: Option<i64>,
-    pub page: Option<i64>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -30,21 +30,20 @@ impl<'a> UserGetLovedTracks<'a> {
         self
     }
 
-    pub fn limit(mut self, limit: i64) -> Self {
+    pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
     }
 
-    pub fn page(mut self, page: i64) -> Self {
+    pub fn page(mut self, page: u32) -> Self {
         self.page = Some(page);
         self
     }
 
     fn validate(&self) -> Result<()> {
-        if self.user.is_none() || self.user.as_ref().unwrap().is_empty() {
-            return Err(Error::Generic("The user name is required.".to_string()));
-        }
-
+        validation::required("user", &self.user)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
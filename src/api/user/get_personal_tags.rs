@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -12,8 +12,8 @@ pub struct UserGetPersonalTags<'a> {
     pub tag: Option<String>,
     //TODO: make an enum to store tagging type
     pub taggingtype: Option<String>,
-    pub limit: Option<String>,
-    pub page: Option<String>,
+    pub limit: Option<u32>,
+    pub page: Option<u32>,
     method: LastfmMethod,
 }
 
@@ -40,22 +40,27 @@ impl<'a> UserGetPersonalTags<'a> {
         self
     }
 
-    pub fn limit(mut self, limit: &str) -> Self {
-        self.limit = Some(limit.to_string());
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
         self
     }
 
-    pub fn page(mut self, page: &str) -> Self {
-        self.page = Some(page.to_string());
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
         self
     }
 
     fn validate(&self) -> Result<()> {
-        if self.tag.is_none() && (self.user.is_none()) {
-            return Err(Error::Generic(
-                "Either 'mbid' or 'artist' must be provided.".to_string(),
-            ));
-        }
+        validation::required("user", &self.user)?;
+        validation::required("tag", &self.tag)?;
+        validation::required("taggingtype", &self.taggingtype)?;
+        validation::one_of(
+            "taggingtype",
+            &self.taggingtype,
+            &["artist", "album", "track"],
+        )?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
@@ -68,8 +73,8 @@ impl<'a> UserGetPersonalTags<'a> {
             .add_optional("user", self.user)
             .add_optional("tag", self.tag)
             .add_optional("taggingtype", self.taggingtype)
-            .add_optional("limit", self.limit)
-            .add_optional("page", self.page);
+            .add_optional("limit", self.limit.map(|l| l.to_string()))
+            .add_optional("page", self.page.map(|p| p.to_string()));
 
         let mut params = builder.build();
 
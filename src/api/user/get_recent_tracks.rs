@@ -1,9 +1,11 @@
+use std::collections::HashSet;
+
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Error, Lastfm, RecentTrack, Result, UserGetRecentTracksResponse,
 };
+use futures::{stream, Stream, TryStreamExt};
 use reqwest::Method;
-use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct UserGetRecentTracks<'a> {
@@ -62,20 +64,22 @@ impl<'a> UserGetRecentTracks<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.username.is_none() {
-            return Err(Error::Generic("Username is required.".to_string()));
-        }
+        validation::required("user", &self.username)?;
+        validation::range("limit", &self.limit, 1, Some(200))?;
+        validation::page(&self.page)?;
+        validation::range("extended", &self.extended, 0, Some(1))?;
+        validation::time_range(&self.from, &self.to)?;
         Ok(())
     }
 
-    pub async fn send(self) -> Result<APIResponse<Value>> {
+    pub async fn send(self) -> Result<APIResponse<UserGetRecentTracksResponse>> {
         self.validate()?;
 
         let mut builder = ParameterBuilder::new();
 
         // Add only the necessary parameters
         builder = builder
-            .add_optional("username", self.username)
+            .add_optional("user", self.username)
             .add_optional("limit", self.limit.map(|l| l.to_string()))
             .add_optional("page", self.page.map(|p| p.to_string()))
             .add_optional("from", self.from.map(|f| f.to_string()))
@@ -92,4 +96,208 @@ impl<'a> UserGetRecentTracks<'a> {
 
         Ok(response)
     }
+
+    /// Streams the scrobbled tracks of every page, newest first, starting at `page`.
+    ///
+    /// The now-playing entry that Last.fm repeats at the top of each page is skipped. When the
+    /// stream starts at the first page and `to` is unset, `to` is pinned just past the newest
+    /// scrobble on that page, so tracks scrobbled while paging do not shift the later pages.
+    /// A track that still shows up on two consecutive pages is only yielded once.
+    pub fn into_stream(self) -> impl Stream<Item = Result<RecentTrack>> + 'a {
+        stream::try_unfold(Some((self, HashSet::new())), |state| async move {
+            let Some((request, previous)) = state else {
+                return Ok(None);
+            };
+
+            let recent = match request.clone().send().await? {
+                APIResponse::Success(response) => response.recenttracks,
+                APIResponse::Error(err) => return Err(Error::ApiError(err)),
+            };
+
+            let scrobbles: Vec<RecentTrack> = recent
+                .tracks
+                .into_iter()
+                .filter(|track| !track.now_playing)
+                .collect();
+            let seen: HashSet<_> = scrobbles.iter().map(track_key).collect();
+            let is_last = scrobbles.is_empty() || !recent.page_info.has_next_page();
+
+            let mut next = request;
+            // `to` is exclusive. Pinning it from a later page would open a smaller window whose
+            // page numbers no longer line up with the ones being walked.
+            if next.to.is_none() && recent.page_info.page == 1 {
+                next.to = scrobbles
+                    .iter()
+                    .filter_map(|track| track.played_at)
+                    .max()
+                    .map(|newest| newest + 1);
+            }
+            next.page = Some(recent.page_info.page as u32 + 1);
+
+            let tracks: Vec<Result<RecentTrack>> = scrobbles
+                .into_iter()
+                .filter(|track| !previous.contains(&track_key(track)))
+                .map(Ok)
+                .collect();
+
+            Ok(Some((
+                stream::iter(tracks),
+                (!is_last).then_some((next, seen)),
+            )))
+        })
+        .try_flatten()
+    }
+}
+
+fn track_key(track: &RecentTrack) -> (Option<u64>, String, String) {
+    (
+        track.played_at,
+        track.artist.name.clone(),
+        track.name.clone(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MockServer, ScrobbleEntry};
+    use futures::{StreamExt, TryStreamExt};
+
+    #[tokio::test]
+    async fn test_stream_walks_all_pages() {
+        let server = MockServer::start("test_api_key", "test_api_secret")
+            .await
+            .unwrap();
+        let mut lastfm = server.lastfm().unwrap();
+        lastfm.set_sk(server.create_session("uppercase_"));
+
+        let entries: Vec<_> = (0..5)
+            .map(|i| ScrobbleEntry::new("Cher", format!("Track {}", i), 1_700_000_000 + i))
+            .collect();
+        lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries)
+            .send()
+            .await
+            .unwrap();
+        lastfm
+            .track()
+            .update_now_playing()
+            .artist("ABBA")
+            .track("Dancing Queen")
+            .send()
+            .await
+            .unwrap();
+
+        let names: Vec<String> = lastfm
+            .user()
+            .get_recent_tracks()
+            .username("uppercase_")
+            .limit(2)
+            .into_stream()
+            .map_ok(|track| track.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            names,
+            ["Track 4", "Track 3", "Track 2", "Track 1", "Track 0"]
+        );
+
+        let bounded: Vec<String> = lastfm
+            .user()
+            .get_recent_tracks()
+            .username("uppercase_")
+            .limit(2)
+            .from(1_700_000_001)
+            .to(1_700_000_004)
+            .into_stream()
+            .map_ok(|track| track.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(bounded, ["Track 3", "Track 2", "Track 1"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_from_later_page() {
+        let server = MockServer::start("test_api_key", "test_api_secret")
+            .await
+            .unwrap();
+        let mut lastfm = server.lastfm().unwrap();
+        lastfm.set_sk(server.create_session("uppercase_"));
+
+        let entries: Vec<_> = (0..10)
+            .map(|i| ScrobbleEntry::new("Cher", format!("Track {}", i), 1_700_000_000 + i))
+            .collect();
+        lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries)
+            .send()
+            .await
+            .unwrap();
+
+        let names: Vec<String> = lastfm
+            .user()
+            .get_recent_tracks()
+            .username("uppercase_")
+            .limit(2)
+            .page(3)
+            .into_stream()
+            .map_ok(|track| track.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            names,
+            ["Track 5", "Track 4", "Track 3", "Track 2", "Track 1", "Track 0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_ignores_scrobbles_made_while_paging() {
+        let server = MockServer::start("test_api_key", "test_api_secret")
+            .await
+            .unwrap();
+        let mut lastfm = server.lastfm().unwrap();
+        lastfm.set_sk(server.create_session("uppercase_"));
+
+        let entries: Vec<_> = (0..4)
+            .map(|i| ScrobbleEntry::new("Cher", format!("Track {}", i), 1_700_000_000 + i))
+            .collect();
+        lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries)
+            .send()
+            .await
+            .unwrap();
+
+        let mut stream = Box::pin(
+            lastfm
+                .user()
+                .get_recent_tracks()
+                .username("uppercase_")
+                .limit(2)
+                .into_stream(),
+        );
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.name, "Track 3");
+
+        lastfm
+            .track()
+            .scrobble_batch()
+            .entries(vec![ScrobbleEntry::new("Cher", "Track 4", 1_700_000_004)])
+            .send()
+            .await
+            .unwrap();
+
+        let rest: Vec<String> = stream
+            .map_ok(|track| track.name)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rest, ["Track 2", "Track 1", "Track 0"]);
+    }
 }
@@ -1,7 +1,9 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Period, Result,
 };
+
+use super::chart_range::{self, period_and_range, ChartKind, RangeChart};
 use reqwest::Method;
 use serde_json::Value;
 
@@ -9,9 +11,12 @@ use serde_json::Value;
 pub struct UserGetTopAlbums<'a> {
     lastfm: &'a Lastfm,
     pub user: Option<String>,
-    pub period: Option<String>,
+    pub period: Option<Period>,
+    /// A custom `(from, to)` range, as Unix timestamps, used instead of `period`.
+    pub range: Option<(u64, u64)>,
     pub limit: Option<u32>,
     pub page: Option<u32>,
+    range_chart: RangeChart,
     method: LastfmMethod,
 }
 
@@ -21,8 +26,10 @@ impl<'a> UserGetTopAlbums<'a> {
             lastfm,
             user: None,
             period: None,
+            range: None,
             limit: Some(50),
             page: Some(1),
+            range_chart: RangeChart::default(),
             method: LastfmMethod::UserGetTopAlbums,
         }
     }
@@ -32,10 +39,7 @@ impl<'a> UserGetTopAlbums<'a> {
         self
     }
 
-    pub fn period(mut self, period: &str) -> Self {
-        self.period = Some(period.to_string());
-        self
-    }
+    period_and_range!();
 
     pub fn limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
@@ -48,20 +52,34 @@ impl<'a> UserGetTopAlbums<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.user.is_none() {
-            return Err(Error::Generic("Username is required.".to_string()));
-        }
+        validation::required("user", &self.user)?;
+        chart_range::validate(self.range)?;
+        validation::limit(&self.limit)?;
+        validation::page(&self.page)?;
         Ok(())
     }
 
     pub async fn send(self) -> Result<APIResponse<Value>> {
         self.validate()?;
 
+        if let Some(range) = self.range {
+            return chart_range::top_chart(
+                self.lastfm,
+                ChartKind::Album,
+                &self.range_chart,
+                self.user.unwrap_or_default(),
+                range,
+                self.limit.unwrap_or(50),
+                self.page.unwrap_or(1),
+            )
+            .await;
+        }
+
         let mut builder = ParameterBuilder::new();
 
         builder = builder
             .add_optional("user", self.user)
-            .add_optional("period", self.period)
+            .add_optional("period", self.period.map(|p| p.to_string()))
             .add_optional("limit", self.limit.map(|l| l.to_string()))
             .add_optional("page", self.page.map(|p| p.to_string()));
 
@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -34,9 +34,8 @@ impl<'a> UserGetTopTags<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.user.is_none() {
-            return Err(Error::Generic("Username is required.".to_string()));
-        }
+        validation::required("user", &self.user)?;
+        validation::limit(&self.limit)?;
         Ok(())
     }
 
@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -27,9 +27,7 @@ impl<'a> UserGetWeeklyChartList<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.user.is_none() {
-            return Err(Error::Generic("Username is required.".to_string()));
-        }
+        validation::required("user", &self.user)?;
         Ok(())
     }
 
@@ -38,7 +36,7 @@ impl<'a> UserGetWeeklyChartList<'a> {
 
         let mut builder = ParameterBuilder::new();
 
-        builder = builder.add("user", self.user.unwrap());
+        builder = builder.add_optional("user", self.user);
 
         let mut params = builder.build();
 
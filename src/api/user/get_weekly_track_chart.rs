@@ -1,6 +1,6 @@
 use crate::{
-    api::{LastfmMethod, ParameterBuilder},
-    APIResponse, Error, Lastfm, Result,
+    api::{validation, LastfmMethod, ParameterBuilder},
+    APIResponse, Lastfm, Result,
 };
 use reqwest::Method;
 use serde_json::Value;
@@ -41,9 +41,9 @@ impl<'a> UserGetWeeklyTrackChart<'a> {
     }
 
     fn validate(&self) -> Result<()> {
-        if self.user.is_none() {
-            return Err(Error::Generic("Username is required.".to_string()));
-        }
+        validation::required("user", &self.user)?;
+        validation::together(&[("from", self.from.is_some()), ("to", self.to.is_some())])?;
+        validation::time_range(&self.from, &self.to)?;
         Ok(())
     }
 
@@ -53,7 +53,7 @@ impl<'a> UserGetWeeklyTrackChart<'a> {
         let mut builder = ParameterBuilder::new();
 
         builder = builder
-            .add("user", self.user.unwrap())
+            .add_optional("user", self.user)
             .add_optional("from", self.from)
             .add_optional("to", self.to);
 
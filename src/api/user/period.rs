@@ -0,0 +1,82 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, ValidationError};
+
+/// The time span of the user top-* charts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Period {
+    #[default]
+    Overall,
+    SevenDays,
+    OneMonth,
+    ThreeMonths,
+    SixMonths,
+    TwelveMonths,
+}
+
+impl Period {
+    pub const ALL: [Period; 6] = [
+        Period::Overall,
+        Period::SevenDays,
+        Period::OneMonth,
+        Period::ThreeMonths,
+        Period::SixMonths,
+        Period::TwelveMonths,
+    ];
+
+    /// The value of the `period` parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Period::Overall => "overall",
+            Period::SevenDays => "7day",
+            Period::OneMonth => "1month",
+            Period::ThreeMonths => "3month",
+            Period::SixMonths => "6month",
+            Period::TwelveMonths => "12month",
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Period {
+    type Err = Error;
+
+    /// Parses a period from its API name, e.g. `"7day"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Period::ALL
+            .into_iter()
+            .find(|period| period.as_str() == s)
+            .ok_or_else(|| {
+                ValidationError::Invalid {
+                    field: "period",
+                    value: s.to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_round_trip() {
+        for period in Period::ALL {
+            assert_eq!(period.to_string().parse::<Period>().unwrap(), period);
+        }
+        assert!(matches!(
+            "7days".parse::<Period>(),
+            Err(Error::Validation(ValidationError::Invalid {
+                field: "period",
+                ..
+            }))
+        ));
+    }
+}
@@ -0,0 +1,261 @@
+//! Checks shared by the `validate()` methods of the request builders.
+
+use crate::ValidationError;
+
+pub(crate) type Validation = std::result::Result<(), ValidationError>;
+
+/// The largest `limit` Last.fm accepts for paginated methods.
+pub(crate) const MAX_LIMIT: i64 = 1000;
+
+/// The largest number of tags accepted by the `addTags` methods.
+pub(crate) const MAX_TAGS: usize = 10;
+
+/// A value that can be checked against a numeric range.
+pub(crate) trait Number {
+    fn to_i64(&self) -> Option<i64>;
+}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(impl Number for $ty {
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+        })*
+    };
+}
+
+impl_number!(u8, u32, u64, i64);
+
+impl Number for String {
+    fn to_i64(&self) -> Option<i64> {
+        self.trim().parse().ok()
+    }
+}
+
+/// Whether an optional text field is set to a non-blank value.
+pub(crate) fn present(value: &Option<String>) -> bool {
+    value.as_ref().is_some_and(|value| !value.trim().is_empty())
+}
+
+pub(crate) fn required(field: &'static str, value: &Option<String>) -> Validation {
+    if present(value) {
+        Ok(())
+    } else {
+        Err(ValidationError::Missing(field))
+    }
+}
+
+pub(crate) fn required_value<T>(field: &'static str, value: &Option<T>) -> Validation {
+    match value {
+        Some(_) => Ok(()),
+        None => Err(ValidationError::Missing(field)),
+    }
+}
+
+/// Requires at least one complete alternative, e.g. `mbid` or `artist` and `album`.
+pub(crate) fn any_of(alternatives: &[(&[&'static str], bool)]) -> Validation {
+    if alternatives.iter().any(|(_, complete)| *complete) {
+        Ok(())
+    } else {
+        Err(ValidationError::MissingOneOf(
+            alternatives
+                .iter()
+                .map(|(fields, _)| fields.to_vec())
+                .collect(),
+        ))
+    }
+}
+
+/// Requires the fields to be either all set or all unset.
+pub(crate) fn together(fields: &[(&'static str, bool)]) -> Validation {
+    let set = fields.iter().filter(|(_, set)| *set).count();
+    if set == 0 || set == fields.len() {
+        Ok(())
+    } else {
+        Err(ValidationError::RequiredTogether(
+            fields.iter().map(|(field, _)| *field).collect(),
+        ))
+    }
+}
+
+pub(crate) fn range<T: Number + ToString>(
+    field: &'static str,
+    value: &Option<T>,
+    min: i64,
+    max: Option<i64>,
+) -> Validation {
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+
+    match value.to_i64() {
+        Some(number) if number >= min && max.is_none_or(|max| number <= max) => Ok(()),
+        Some(number) => Err(ValidationError::OutOfRange {
+            field,
+            value: number,
+            min,
+            max,
+        }),
+        None => Err(ValidationError::Invalid {
+            field,
+            value: value.to_string(),
+        }),
+    }
+}
+
+pub(crate) fn limit<T: Number + ToString>(value: &Option<T>) -> Validation {
+    range("limit", value, 1, Some(MAX_LIMIT))
+}
+
+pub(crate) fn page<T: Number + ToString>(value: &Option<T>) -> Validation {
+    range("page", value, 1, None)
+}
+
+/// Requires `from` not to be after `to` when both are set.
+pub(crate) fn time_range<T: Number + ToString>(from: &Option<T>, to: &Option<T>) -> Validation {
+    range("from", from, 0, None)?;
+    range("to", to, 0, None)?;
+
+    match (
+        from.as_ref().and_then(Number::to_i64),
+        to.as_ref().and_then(Number::to_i64),
+    ) {
+        (Some(from), Some(to)) if from > to => Err(ValidationError::InvalidRange {
+            from: "from",
+            to: "to",
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Limits the number of values in a comma-separated list.
+pub(crate) fn max_items(field: &'static str, value: &Option<String>, max: usize) -> Validation {
+    let actual = value.as_ref().map_or(0, |value| value.split(',').count());
+    if actual > max {
+        Err(ValidationError::TooMany { field, max, actual })
+    } else {
+        Ok(())
+    }
+}
+
+/// Requires the value, when set, to be one of `allowed`.
+pub(crate) fn one_of(field: &'static str, value: &Option<String>, allowed: &[&str]) -> Validation {
+    match value {
+        Some(value) if !allowed.contains(&value.as_str()) => Err(ValidationError::Invalid {
+            field,
+            value: value.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::lastfm_with;
+    use crate::{Error, Lastfm, LastfmMethod, MockTransport};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_builder_reports_fields() {
+        let lastfm = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .build()
+            .unwrap();
+
+        match lastfm.album().get_info().artist("Cher").send().await {
+            Err(Error::Validation(err)) => {
+                assert_eq!(err.fields(), vec!["mbid", "artist", "album"])
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+
+        match lastfm
+            .user()
+            .get_recent_tracks()
+            .username("rj")
+            .from(20)
+            .to(10)
+            .send()
+            .await
+        {
+            Err(Error::Validation(err)) => assert_eq!(err.fields(), vec!["from", "to"]),
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mbid_only_alternative_is_sent() {
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond(
+                    LastfmMethod::TrackGetTopTags,
+                    json!({ "toptags": { "tag": [] } }),
+                )
+                .respond(
+                    LastfmMethod::ArtistGetTopTags,
+                    json!({ "toptags": { "tag": [] } }),
+                ),
+        );
+        let lastfm = lastfm_with(transport.clone());
+
+        lastfm
+            .track()
+            .get_top_tags()
+            .mbid("track-mbid")
+            .send()
+            .await
+            .unwrap();
+        let request = transport.last_request().unwrap();
+        assert_eq!(request["mbid"], "track-mbid");
+        assert!(!request.contains_key("artist") && !request.contains_key("track"));
+
+        lastfm
+            .artist()
+            .get_top_tags()
+            .mbid("artist-mbid")
+            .send()
+            .await
+            .unwrap();
+        let request = transport.last_request().unwrap();
+        assert_eq!(request["mbid"], "artist-mbid");
+        assert!(!request.contains_key("artist"));
+    }
+
+    #[test]
+    fn test_any_of() {
+        let err = any_of(&[(&["mbid"], false), (&["artist", "album"], false)]).unwrap_err();
+        assert_eq!(err.fields(), vec!["mbid", "artist", "album"]);
+        assert_eq!(err.to_string(), "one of mbid or artist+album is required");
+        assert!(any_of(&[(&["mbid"], false), (&["artist", "album"], true)]).is_ok());
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert!(limit(&Some(50_i64)).is_ok());
+        assert_eq!(
+            limit(&Some(1001_u32)),
+            Err(ValidationError::OutOfRange {
+                field: "limit",
+                value: 1001,
+                min: 1,
+                max: Some(MAX_LIMIT),
+            })
+        );
+        assert!(page(&Some(0_i64)).is_err());
+        assert!(matches!(
+            page(&Some("two".to_string())),
+            Err(ValidationError::Invalid { field: "page", .. })
+        ));
+        assert_eq!(
+            time_range(&Some(20_u64), &Some(10_u64)),
+            Err(ValidationError::InvalidRange {
+                from: "from",
+                to: "to"
+            })
+        );
+    }
+}
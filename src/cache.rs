@@ -0,0 +1,267 @@
+//! Caching of API responses for read-only methods.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::api::LastfmMethod;
+
+/// Storage for cached responses. Implement it to plug in a shared cache such as Redis.
+pub trait CacheBackend: Send + Sync {
+    /// Returns the cached response for `key`, unless it is missing or expired.
+    fn get(&self, key: &str) -> Option<Value>;
+
+    /// Stores `value` under `key` for `ttl`.
+    fn insert(&self, key: String, value: Value, ttl: Duration);
+
+    /// Removes every cached response.
+    fn clear(&self);
+}
+
+/// An in-memory [`CacheBackend`] that evicts the least recently used response when full.
+#[derive(Debug)]
+pub struct LruCache {
+    capacity: usize,
+    inner: Mutex<LruInner>,
+}
+
+#[derive(Debug, Default)]
+struct LruInner {
+    entries: HashMap<String, LruEntry>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    value: Value,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity: capacity.max(1),
+            inner: Mutex::new(LruInner::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruInner> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl LruInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
+
+impl CacheBackend for LruCache {
+    fn get(&self, key: &str) -> Option<Value> {
+        let mut inner = self.lock();
+        let tick = inner.next_tick();
+
+        let (expired, last_used) = match inner.entries.get(key) {
+            Some(entry) => (entry.expires_at <= Instant::now(), entry.last_used),
+            None => return None,
+        };
+
+        if expired {
+            inner.remove(key);
+            return None;
+        }
+
+        inner.recency.remove(&last_used);
+        inner.recency.insert(tick, key.to_string());
+        let entry = inner.entries.get_mut(key)?;
+        entry.last_used = tick;
+        Some(entry.value.clone())
+    }
+
+    fn insert(&self, key: String, value: Value, ttl: Duration) {
+        let mut inner = self.lock();
+        inner.remove(&key);
+
+        while inner.entries.len() >= self.capacity {
+            match inner.recency.pop_first() {
+                Some((_, oldest)) => {
+                    inner.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+
+        let tick = inner.next_tick();
+        inner.recency.insert(tick, key.clone());
+        inner.entries.insert(
+            key,
+            LruEntry {
+                value,
+                expires_at: Instant::now() + ttl,
+                last_used: tick,
+            },
+        );
+    }
+
+    fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.recency.clear();
+    }
+}
+
+/// Caches responses of read-only methods in a [`CacheBackend`].
+///
+/// Each method is cached for the TTL returned by [`LastfmMethod::cache_ttl`] unless
+/// overridden with [`ResponseCache::ttl`]. Methods that require authentication are never cached.
+#[derive(Clone)]
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    ttl_overrides: HashMap<LastfmMethod, Option<Duration>>,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl_overrides", &self.ttl_overrides)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResponseCache {
+    pub fn new(backend: Arc<dyn CacheBackend>) -> Self {
+        ResponseCache {
+            backend,
+            ttl_overrides: HashMap::new(),
+        }
+    }
+
+    /// A cache backed by an [`LruCache`] holding up to `capacity` responses.
+    pub fn in_memory(capacity: usize) -> Self {
+        ResponseCache::new(Arc::new(LruCache::new(capacity)))
+    }
+
+    /// Overrides the TTL for `method`. `None` disables caching for it.
+    pub fn ttl(mut self, method: LastfmMethod, ttl: Option<Duration>) -> Self {
+        self.ttl_overrides.insert(method, ttl);
+        self
+    }
+
+    pub fn backend(&self) -> &Arc<dyn CacheBackend> {
+        &self.backend
+    }
+
+    /// The TTL used for `method`, or `None` if its responses are not cached.
+    pub fn ttl_for(&self, method: &LastfmMethod) -> Option<Duration> {
+        if method.requires_auth() {
+            return None;
+        }
+        match self.ttl_overrides.get(method) {
+            Some(ttl) => *ttl,
+            None => method.cache_ttl(),
+        }
+    }
+
+    /// Builds the cache key from the request parameters, leaving out the session key and
+    /// signature so that secrets never end up in the cache.
+    pub fn key(params: &HashMap<String, String>) -> String {
+        let mut pairs: Vec<(&String, &String)> = params
+            .iter()
+            .filter(|(key, _)| key.as_str() != "api_sig" && key.as_str() != "sk")
+            .collect();
+        pairs.sort();
+
+        pairs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<Value> {
+        self.backend.get(key)
+    }
+
+    pub(crate) fn insert(&self, key: String, value: Value, ttl: Duration) {
+        self.backend.insert(key, value, ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let cache = LruCache::new(2);
+        let ttl = Duration::from_secs(60);
+
+        cache.insert("a".to_string(), json!(1), ttl);
+        cache.insert("b".to_string(), json!(2), ttl);
+        assert_eq!(cache.get("a"), Some(json!(1)));
+
+        cache.insert("c".to_string(), json!(3), ttl);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(json!(1)));
+        assert_eq!(cache.get("c"), Some(json!(3)));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_lru_expires_entries() {
+        let cache = LruCache::new(2);
+        cache.insert("a".to_string(), json!(1), Duration::ZERO);
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_key_is_sorted_and_excludes_secrets() {
+        let mut params = HashMap::new();
+        params.insert("method".to_string(), "artist.getInfo".to_string());
+        params.insert("artist".to_string(), "Cher".to_string());
+        params.insert("sk".to_string(), "secret".to_string());
+        params.insert("api_sig".to_string(), "signature".to_string());
+
+        assert_eq!(
+            ResponseCache::key(&params),
+            "artist=Cher&method=artist.getInfo"
+        );
+    }
+
+    #[test]
+    fn test_ttl_for() {
+        let cache = ResponseCache::in_memory(10)
+            .ttl(LastfmMethod::TagGetInfo, None)
+            .ttl(LastfmMethod::TrackScrobble, Some(Duration::from_secs(60)));
+
+        assert!(cache.ttl_for(&LastfmMethod::ArtistGetInfo).is_some());
+        assert_eq!(cache.ttl_for(&LastfmMethod::TagGetInfo), None);
+        assert_eq!(cache.ttl_for(&LastfmMethod::TrackScrobble), None);
+        assert!(
+            cache.ttl_for(&LastfmMethod::UserGetRecentTracks)
+                < cache.ttl_for(&LastfmMethod::ArtistGetInfo)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Recording of API responses to fixtures, and replaying them in tests.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::transport::{Transport, TransportFuture, TransportResponse};
+use crate::{Error, Result};
+
+/// Parameters that are never written to a fixture, nor used to look one up.
+const SECRET_PARAMS: [&str; 5] = ["api_key", "api_sig", "sk", "password", "token"];
+
+/// Stands in for secrets removed from recorded responses.
+const REDACTED: &str = "REDACTED";
+
+/// A [`Transport`] that records responses to a fixture directory, or replays them from it.
+///
+/// Each fixture holds the method, the request parameters without credentials, and the JSON
+/// response with any session key or auth token replaced by `"REDACTED"`. In replay mode a
+/// request without a matching fixture fails with [`Error::MissingFixture`] instead of reaching
+/// the network.
+#[derive(Debug)]
+pub struct Cassette {
+    dir: PathBuf,
+    mode: Mode,
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record(Arc<dyn Transport>),
+    Replay,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    params: BTreeMap<String, String>,
+    status: u16,
+    response: Value,
+}
+
+impl Cassette {
+    /// Sends requests through `inner` and writes each response to `dir`.
+    pub fn record<P: AsRef<Path>>(dir: P, inner: Arc<dyn Transport>) -> Self {
+        Cassette {
+            dir: dir.as_ref().to_path_buf(),
+            mode: Mode::Record(inner),
+        }
+    }
+
+    /// Serves the responses previously recorded to `dir`.
+    pub fn replay<P: AsRef<Path>>(dir: P) -> Self {
+        Cassette {
+            dir: dir.as_ref().to_path_buf(),
+            mode: Mode::Replay,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Record(_))
+    }
+
+    /// The file the response to `params` is recorded to.
+    pub fn fixture_path(&self, params: &HashMap<String, String>) -> PathBuf {
+        self.path_for(&public_params(params))
+    }
+
+    fn path_for(&self, params: &BTreeMap<String, String>) -> PathBuf {
+        let method = params
+            .get("method")
+            .map(String::as_str)
+            .unwrap_or("unknown");
+        let digest = md5::compute(fixture_key(params));
+
+        self.dir.join(format!("{}-{:x}.json", method, digest))
+    }
+
+    fn load(&self, params: &HashMap<String, String>) -> Result<TransportResponse> {
+        let public = public_params(params);
+
+        let fixture: Fixture = match fs::read_to_string(self.path_for(&public)) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::MissingFixture(fixture_key(&public)));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if fixture.params != public {
+            return Err(Error::MissingFixture(fixture_key(&public)));
+        }
+
+        let body = match fixture.response {
+            Value::String(body) => body,
+            response => response.to_string(),
+        };
+
+        Ok(TransportResponse {
+            status: fixture.status,
+            body,
+        })
+    }
+
+    fn save(&self, params: &HashMap<String, String>, response: &TransportResponse) -> Result<()> {
+        let params = public_params(params);
+        let mut body = serde_json::from_str(&response.body)
+            .unwrap_or_else(|_| Value::String(response.body.clone()));
+        redact(&mut body);
+        let fixture = Fixture {
+            method: params.get("method").cloned().unwrap_or_default(),
+            status: response.status,
+            response: body,
+            params,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.path_for(&fixture.params),
+            serde_json::to_string_pretty(&fixture)?,
+        )?;
+        Ok(())
+    }
+}
+
+impl Transport for Cassette {
+    fn send<'a>(
+        &'a self,
+        url: &'a str,
+        http_method: Method,
+        params: &'a HashMap<String, String>,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            match &self.mode {
+                Mode::Record(inner) => {
+                    let response = inner.send(url, http_method, params).await?;
+                    self.save(params, &response)?;
+                    Ok(response)
+                }
+                Mode::Replay => self.load(params),
+            }
+        })
+    }
+}
+
+fn public_params(params: &HashMap<String, String>) -> BTreeMap<String, String> {
+    params
+        .iter()
+        .filter(|(key, _)| !SECRET_PARAMS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Replaces the session key of `auth.get*Session` and the token of `auth.getToken`.
+fn redact(response: &mut Value) {
+    for pointer in ["/session/key", "/token"] {
+        if let Some(secret) = response.pointer_mut(pointer) {
+            *secret = Value::String(REDACTED.to_string());
+        }
+    }
+}
+
+fn fixture_key(params: &BTreeMap<String, String>) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::lastfm_with;
+    use crate::{APIResponse, LastfmMethod, MockTransport};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let mock = MockTransport::new().respond(
+            LastfmMethod::TagGetInfo,
+            json!({ "tag": { "name": "disco" } }),
+        );
+
+        let recorder = lastfm_with(Arc::new(Cassette::record(dir.path(), Arc::new(mock))));
+        recorder.tag().get_info().tag("disco").send().await.unwrap();
+
+        let fixtures: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(fixtures.len(), 1);
+        let contents = fs::read_to_string(fixtures[0].as_ref().unwrap().path()).unwrap();
+        assert!(!contents.contains("test_api_key"));
+
+        let player = lastfm_with(Arc::new(Cassette::replay(dir.path())));
+        let response = player.tag().get_info().tag("disco").send().await.unwrap();
+        assert!(matches!(response, APIResponse::Success(_)));
+    }
+
+    #[tokio::test]
+    async fn test_record_redacts_session_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let mock = MockTransport::new().respond(
+            LastfmMethod::AuthGetSession,
+            json!({
+                "session": { "name": "uppercase_", "key": "live_session_key", "subscriber": 0 }
+            }),
+        );
+
+        let recorder = lastfm_with(Arc::new(Cassette::record(dir.path(), Arc::new(mock))));
+        let response = recorder
+            .auth()
+            .get_session()
+            .token("live_token")
+            .send()
+            .await
+            .unwrap();
+        assert!(matches!(response, APIResponse::Success(_)));
+
+        let fixtures: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(fixtures.len(), 1);
+        let contents = fs::read_to_string(fixtures[0].as_ref().unwrap().path()).unwrap();
+        assert!(!contents.contains("live_session_key"));
+        assert!(!contents.contains("live_token"));
+        assert!(contents.contains(REDACTED));
+    }
+
+    #[tokio::test]
+    async fn test_replay_fails_on_unknown_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let player = lastfm_with(Arc::new(Cassette::replay(dir.path())));
+
+        let result = player.tag().get_info().tag("disco").send().await;
+        assert!(matches!(result, Err(Error::MissingFixture(_))));
+    }
+}
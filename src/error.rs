@@ -24,6 +24,9 @@ pub enum Error {
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
 
+    #[error("HTTP error: status {0}")]
+    HttpStatus(u16),
+
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
 
@@ -33,6 +36,27 @@ pub enum Error {
     #[error("API error: {0}")]
     ApiError(#[from] ApiError),
 
+    #[error("Invalid request: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error("No fixture recorded for request: {0}")]
+    MissingFixture(String),
+
+    #[error("Missing credential: {0}")]
+    MissingCredential(String),
+
+    #[error("The client has no session key; authenticate the user first")]
+    NotAuthenticated,
+
+    #[error("Timed out waiting for the user to authorize the token")]
+    AuthorizationTimeout,
+
+    #[error("The token expired before the user authorized it")]
+    TokenExpired,
+
+    #[error("Last.fm echoed {echoed} scrobbles for the {sent} submitted")]
+    ScrobbleCountMismatch { sent: usize, echoed: usize },
+
     #[error("Unknown error: {0}")]
     UnknownError(String),
 }
@@ -44,3 +68,299 @@ impl fmt::Display for ApiError {
 }
 
 impl std::error::Error for ApiError {}
+
+/// A request builder was given missing, conflicting or out-of-range input.
+///
+/// The offending fields, named as in the Last.fm documentation, are available through
+/// [`ValidationError::fields`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A required field is not set, or is empty.
+    Missing(&'static str),
+    /// None of the alternatives is complete. Each alternative lists the fields it needs.
+    MissingOneOf(Vec<Vec<&'static str>>),
+    /// Fields that must be set together, of which only some are set.
+    RequiredTogether(Vec<&'static str>),
+    /// A numeric field is outside of its allowed range.
+    OutOfRange {
+        field: &'static str,
+        value: i64,
+        min: i64,
+        max: Option<i64>,
+    },
+    /// A field holds a value Last.fm does not accept.
+    Invalid { field: &'static str, value: String },
+    /// A list field holds more values than Last.fm accepts.
+    TooMany {
+        field: &'static str,
+        max: usize,
+        actual: usize,
+    },
+    /// The start of a time range is after its end.
+    InvalidRange {
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+impl ValidationError {
+    /// The names of the fields that caused the error.
+    pub fn fields(&self) -> Vec<&'static str> {
+        match self {
+            ValidationError::Missing(field)
+            | ValidationError::OutOfRange { field, .. }
+            | ValidationError::Invalid { field, .. }
+            | ValidationError::TooMany { field, .. } => vec![field],
+            ValidationError::MissingOneOf(alternatives) => {
+                alternatives.iter().flatten().copied().collect()
+            }
+            ValidationError::RequiredTogether(fields) => fields.clone(),
+            ValidationError::InvalidRange { from, to } => vec![from, to],
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Missing(field) => write!(f, "'{}' is required", field),
+            ValidationError::MissingOneOf(alternatives) => {
+                let alternatives: Vec<String> =
+                    alternatives.iter().map(|fields| fields.join("+")).collect();
+                write!(f, "one of {} is required", alternatives.join(" or "))
+            }
+            ValidationError::RequiredTogether(fields) => {
+                write!(f, "{} must be set together", fields.join(", "))
+            }
+            ValidationError::OutOfRange {
+                field,
+                value,
+                min,
+                max: Some(max),
+            } => write!(
+                f,
+                "'{}' must be between {} and {}, got {}",
+                field, min, max, value
+            ),
+            ValidationError::OutOfRange {
+                field,
+                value,
+                min,
+                max: None,
+            } => write!(f, "'{}' must be at least {}, got {}", field, min, value),
+            ValidationError::Invalid { field, value } => {
+                write!(f, "'{}' has an invalid value '{}'", field, value)
+            }
+            ValidationError::TooMany { field, max, actual } => write!(
+                f,
+                "'{}' accepts at most {} values, got {}",
+                field, max, actual
+            ),
+            ValidationError::InvalidRange { from, to } => {
+                write!(f, "'{}' must not be after '{}'", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl ApiError {
+    /// The typed error code, for matching without magic numbers.
+    pub fn code(&self) -> ApiErrorCode {
+        ApiErrorCode::from(self.error)
+    }
+}
+
+/// The error codes documented by the Last.fm API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    InvalidService,
+    InvalidMethod,
+    AuthenticationFailed,
+    InvalidFormat,
+    InvalidParameters,
+    InvalidResource,
+    OperationFailed,
+    InvalidSessionKey,
+    InvalidApiKey,
+    ServiceOffline,
+    SubscribersOnly,
+    InvalidSignature,
+    UnauthorizedToken,
+    TokenExpired,
+    TemporaryError,
+    LoginRequired,
+    TrialExpired,
+    NotEnoughContent,
+    NotEnoughMembers,
+    NotEnoughFans,
+    NotEnoughNeighbours,
+    NoPeakRadio,
+    RadioNotFound,
+    ApiKeySuspended,
+    Deprecated,
+    RateLimitExceeded,
+    Unknown(i64),
+}
+
+impl ApiErrorCode {
+    pub fn code(&self) -> i64 {
+        match self {
+            ApiErrorCode::InvalidService => 2,
+            ApiErrorCode::InvalidMethod => 3,
+            ApiErrorCode::AuthenticationFailed => 4,
+            ApiErrorCode::InvalidFormat => 5,
+            ApiErrorCode::InvalidParameters => 6,
+            ApiErrorCode::InvalidResource => 7,
+            ApiErrorCode::OperationFailed => 8,
+            ApiErrorCode::InvalidSessionKey => 9,
+            ApiErrorCode::InvalidApiKey => 10,
+            ApiErrorCode::ServiceOffline => 11,
+            ApiErrorCode::SubscribersOnly => 12,
+            ApiErrorCode::InvalidSignature => 13,
+            ApiErrorCode::UnauthorizedToken => 14,
+            ApiErrorCode::TokenExpired => 15,
+            ApiErrorCode::TemporaryError => 16,
+            ApiErrorCode::LoginRequired => 17,
+            ApiErrorCode::TrialExpired => 18,
+            ApiErrorCode::NotEnoughContent => 20,
+            ApiErrorCode::NotEnoughMembers => 21,
+            ApiErrorCode::NotEnoughFans => 22,
+            ApiErrorCode::NotEnoughNeighbours => 23,
+            ApiErrorCode::NoPeakRadio => 24,
+            ApiErrorCode::RadioNotFound => 25,
+            ApiErrorCode::ApiKeySuspended => 26,
+            ApiErrorCode::Deprecated => 27,
+            ApiErrorCode::RateLimitExceeded => 29,
+            ApiErrorCode::Unknown(code) => *code,
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::OperationFailed
+                | ApiErrorCode::ServiceOffline
+                | ApiErrorCode::TemporaryError
+                | ApiErrorCode::RateLimitExceeded
+        )
+    }
+
+    /// Whether the request was rejected because of the API key, signature, token or session.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::AuthenticationFailed
+                | ApiErrorCode::InvalidSessionKey
+                | ApiErrorCode::InvalidApiKey
+                | ApiErrorCode::InvalidSignature
+                | ApiErrorCode::UnauthorizedToken
+                | ApiErrorCode::TokenExpired
+                | ApiErrorCode::LoginRequired
+                | ApiErrorCode::ApiKeySuspended
+        )
+    }
+
+    /// Whether the user must go through the auth flow again to obtain a new session.
+    pub fn requires_reauthentication(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorCode::InvalidSessionKey
+                | ApiErrorCode::TokenExpired
+                | ApiErrorCode::LoginRequired
+        )
+    }
+}
+
+impl From<i64> for ApiErrorCode {
+    fn from(code: i64) -> Self {
+        match code {
+            2 => ApiErrorCode::InvalidService,
+            3 => ApiErrorCode::InvalidMethod,
+            4 => ApiErrorCode::AuthenticationFailed,
+            5 => ApiErrorCode::InvalidFormat,
+            6 => ApiErrorCode::InvalidParameters,
+            7 => ApiErrorCode::InvalidResource,
+            8 => ApiErrorCode::OperationFailed,
+            9 => ApiErrorCode::InvalidSessionKey,
+            10 => ApiErrorCode::InvalidApiKey,
+            11 => ApiErrorCode::ServiceOffline,
+            12 => ApiErrorCode::SubscribersOnly,
+            13 => ApiErrorCode::InvalidSignature,
+            14 => ApiErrorCode::UnauthorizedToken,
+            15 => ApiErrorCode::TokenExpired,
+            16 => ApiErrorCode::TemporaryError,
+            17 => ApiErrorCode::LoginRequired,
+            18 => ApiErrorCode::TrialExpired,
+            20 => ApiErrorCode::NotEnoughContent,
+            21 => ApiErrorCode::NotEnoughMembers,
+            22 => ApiErrorCode::NotEnoughFans,
+            23 => ApiErrorCode::NotEnoughNeighbours,
+            24 => ApiErrorCode::NoPeakRadio,
+            25 => ApiErrorCode::RadioNotFound,
+            26 => ApiErrorCode::ApiKeySuspended,
+            27 => ApiErrorCode::Deprecated,
+            29 => ApiErrorCode::RateLimitExceeded,
+            code => ApiErrorCode::Unknown(code),
+        }
+    }
+}
+
+impl fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiErrorCode::InvalidService => write!(f, "Invalid service - This service does not exist"),
+            ApiErrorCode::InvalidMethod => write!(f, "Invalid method - No method with that name in this package"),
+            ApiErrorCode::AuthenticationFailed => write!(f, "Authentication failed - You do not have permissions to access the service"),
+            ApiErrorCode::InvalidFormat => write!(f, "Invalid format - This service doesn't exist in that format"),
+            ApiErrorCode::InvalidParameters => write!(f, "Invalid parameters - Your request is missing a required parameter"),
+            ApiErrorCode::InvalidResource => write!(f, "Invalid resource specified"),
+            ApiErrorCode::OperationFailed => write!(f, "Operation failed - Something else went wrong"),
+            ApiErrorCode::InvalidSessionKey => write!(f, "Invalid session key - Please re-authenticate"),
+            ApiErrorCode::InvalidApiKey => write!(f, "Invalid API key - You must be granted a valid key by last.fm"),
+            ApiErrorCode::ServiceOffline => write!(f, "Service offline - This service is temporarily offline, try again later"),
+            ApiErrorCode::SubscribersOnly => write!(f, "Subscribers only - This station is only available to paid subscribers"),
+            ApiErrorCode::InvalidSignature => write!(f, "Invalid method signature supplied"),
+            ApiErrorCode::UnauthorizedToken => write!(f, "Unauthorized token - This token has not been authorized"),
+            ApiErrorCode::TokenExpired => write!(f, "Token expired - This token has expired"),
+            ApiErrorCode::TemporaryError => write!(f, "There was a temporary error processing your request, please try again"),
+            ApiErrorCode::LoginRequired => write!(f, "Login required - User requires to be logged in"),
+            ApiErrorCode::TrialExpired => write!(f, "Trial expired - This user has no free radio plays left"),
+            ApiErrorCode::NotEnoughContent => write!(f, "Not enough content - There is not enough content to play this station"),
+            ApiErrorCode::NotEnoughMembers => write!(f, "Not enough members - This group does not have enough members for radio"),
+            ApiErrorCode::NotEnoughFans => write!(f, "Not enough fans - This artist does not have enough fans for radio"),
+            ApiErrorCode::NotEnoughNeighbours => write!(f, "Not enough neighbours - There are not enough neighbours for radio"),
+            ApiErrorCode::NoPeakRadio => write!(f, "No peak radio - This user is not allowed to listen to radio during peak usage"),
+            ApiErrorCode::RadioNotFound => write!(f, "Radio not found - Radio station not found"),
+            ApiErrorCode::ApiKeySuspended => write!(f, "API key suspended - This application is not allowed to make requests to the web services"),
+            ApiErrorCode::Deprecated => write!(f, "Deprecated - This type of request is no longer supported"),
+            ApiErrorCode::RateLimitExceeded => write!(f, "Rate limit exceeded - Your IP has made too many requests in a short period"),
+            ApiErrorCode::Unknown(code) => write!(f, "Unknown error code ({})", code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_code() {
+        let err = ApiError {
+            error: 9,
+            message: "Invalid session key - Please re-authenticate".to_string(),
+            links: None,
+        };
+
+        assert_eq!(err.code(), ApiErrorCode::InvalidSessionKey);
+        assert!(err.code().is_auth_error());
+        assert!(err.code().requires_reauthentication());
+        assert!(!err.code().is_retryable());
+
+        assert!(ApiErrorCode::from(29).is_retryable());
+        assert_eq!(ApiErrorCode::from(99), ApiErrorCode::Unknown(99));
+        assert_eq!(ApiErrorCode::from(99).code(), 99);
+    }
+}
@@ -3,31 +3,68 @@ use reqwest::Method;
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::time::sleep;
 
-use crate::APIResponse;
 use crate::{
     api::{Chart, Geo, LastfmMethod, Library, Tag, Track, User},
     error::{ApiError, Error, Result},
-    Album, Artist, Auth,
+    transport::{ReqwestTransport, Transport},
+    APIResponse, Album, Artist, Auth, RateLimiter, ResponseCache, RetryPolicy, Session,
+    SessionStore,
 };
 
-pub const LASTFM_API_URL: &str = "http://ws.audioscrobbler.com/2.0/";
+pub const LASTFM_API_URL: &str = "https://ws.audioscrobbler.com/2.0/";
+pub const LASTFM_AUTH_URL: &str = "https://www.last.fm/api/auth";
 
-#[derive(Debug, Clone, Default)]
+pub const LIBREFM_API_URL: &str = "https://libre.fm/2.0/";
+pub const LIBREFM_AUTH_URL: &str = "https://libre.fm/api/auth/";
+
+#[derive(Debug, Clone)]
 pub struct Lastfm {
     base_url: String,
+    auth_url: String,
     api_key: String,
     client: ReqwestClient,
-    api_secret: String,
+    transport: Arc<dyn Transport>,
+    api_secret: Option<String>,
     sk: Option<String>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+}
+
+impl Default for Lastfm {
+    fn default() -> Self {
+        let client = ReqwestClient::default();
+        Lastfm {
+            base_url: String::default(),
+            auth_url: String::default(),
+            api_key: String::default(),
+            transport: Arc::new(ReqwestTransport::new(client.clone())),
+            client,
+            api_secret: None,
+            sk: None,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct LastfmBuilder {
     api_key: Option<String>,
+    base_url: Option<String>,
+    auth_url: Option<String>,
     client: Option<ReqwestClient>,
+    transport: Option<Arc<dyn Transport>>,
     api_secret: Option<String>,
     sk: Option<String>,
+    session_store: Option<(Arc<dyn SessionStore>, String)>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
 }
 
 impl LastfmBuilder {
@@ -46,18 +83,88 @@ impl LastfmBuilder {
         self
     }
 
+    /// Sends requests to `base_url` instead of [`LASTFM_API_URL`], e.g. a local mock server
+    /// or another service implementing the 2.0 API.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// The page users visit to authorize the application. Defaults to [`LASTFM_AUTH_URL`].
+    pub fn auth_url(mut self, auth_url: &str) -> Self {
+        self.auth_url = Some(auth_url.to_string());
+        self
+    }
+
     pub fn client(mut self, client: ReqwestClient) -> Self {
         self.client = Some(client);
         self
     }
 
+    /// Sends requests through `transport` instead of the client set with
+    /// [`LastfmBuilder::client`]. Use a [`MockTransport`](crate::MockTransport) to test offline.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Loads the session saved for `username` from `store` when the client is built.
+    ///
+    /// A session key set with [`LastfmBuilder::session_key`] takes precedence.
+    pub fn session_store(mut self, store: Arc<dyn SessionStore>, username: &str) -> Self {
+        self.session_store = Some((store, username.to_string()));
+        self
+    }
+
+    /// Retries requests that fail with transient errors. By default requests are sent once.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Throttles every request sent by the client and its clones through `rate_limiter`.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Caches responses of read-only methods. See [`ResponseCache`] for the default TTLs.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Builds the client. Only the API key is required: without an API secret the client can
+    /// still call methods that need neither a signature nor a session.
     pub fn build(self) -> Result<Lastfm> {
+        let api_key = self
+            .api_key
+            .ok_or_else(|| Error::MissingCredential("api_key".to_string()))?;
+
+        let sk = match (self.sk, self.session_store) {
+            (Some(sk), _) => Some(sk),
+            (None, Some((store, username))) => {
+                store.load(&api_key, &username)?.map(|session| session.key)
+            }
+            (None, None) => None,
+        };
+
+        let client = self.client.unwrap_or_default();
+        let transport = self
+            .transport
+            .unwrap_or_else(|| Arc::new(ReqwestTransport::new(client.clone())));
+
         Ok(Lastfm {
-            api_secret: self.api_secret.expect("API_SECRET is Required."),
-            api_key: self.api_key.expect("API_KEY is required."),
-            client: self.client.unwrap_or_default(),
-            base_url: LASTFM_API_URL.to_string(),
-            sk: self.sk,
+            api_secret: self.api_secret,
+            api_key,
+            client,
+            transport,
+            base_url: self.base_url.unwrap_or_else(|| LASTFM_API_URL.to_string()),
+            auth_url: self.auth_url.unwrap_or_else(|| LASTFM_AUTH_URL.to_string()),
+            sk,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: self.rate_limiter,
+            cache: self.cache,
         })
     }
 }
@@ -66,59 +173,81 @@ impl Lastfm {
     pub fn builder() -> LastfmBuilder {
         LastfmBuilder {
             api_key: None,
+            base_url: None,
+            auth_url: None,
             client: None,
+            transport: None,
             api_secret: None,
             sk: None,
+            session_store: None,
+            retry_policy: None,
+            rate_limiter: None,
+            cache: None,
         }
     }
 
+    /// A builder preset for [Libre.fm](https://libre.fm), which implements the same API.
+    pub fn librefm() -> LastfmBuilder {
+        Lastfm::builder()
+            .base_url(LIBREFM_API_URL)
+            .auth_url(LIBREFM_AUTH_URL)
+    }
+
     pub fn get_client(&self) -> &ReqwestClient {
         &self.client
     }
 
+    pub fn get_transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
     pub fn get_api_key(&self) -> String {
         self.api_key.clone()
     }
 
-    pub fn get_api_secret(&self) -> String {
-        self.api_secret.clone()
+    pub fn get_api_secret(&self) -> Result<String> {
+        self.api_secret
+            .clone()
+            .ok_or_else(|| Error::MissingCredential("api_secret".to_string()))
     }
 
     pub fn get_base_url(&self) -> &String {
         &self.base_url
     }
 
-    pub fn get_sk(&self) -> String {
-        self.sk.clone().expect("A user must be authenticated")
+    pub fn get_auth_url(&self) -> &String {
+        &self.auth_url
     }
 
-    pub fn set_sk(&mut self, sk: String) -> &mut Self {
-        self.sk = Some(sk.to_string());
-        self
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
     }
 
-    pub fn sign_api(&self, params: &mut HashMap<String, String>) -> String {
-        let mut sorted_keys: Vec<String> = params.keys().cloned().collect();
-        sorted_keys.sort();
-        let mut concatenated_string = String::new();
+    pub fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
 
-        for key in sorted_keys {
-            if let Some(value) = params.get(&key) {
-                concatenated_string.push_str(&key);
-                concatenated_string.push_str(value);
-            }
-        }
-        concatenated_string.push_str(&self.get_api_secret());
+    pub fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
 
-        // println!(
-        //     "Concatenated string for API signature: {}",
-        //     concatenated_string
-        // );
+    pub fn get_sk(&self) -> Result<String> {
+        self.sk.clone().ok_or(Error::NotAuthenticated)
+    }
 
-        let string_bytes = concatenated_string.as_bytes();
-        let digest = md5::compute(string_bytes);
+    pub fn set_sk(&mut self, sk: String) -> &mut Self {
+        self.sk = Some(sk.to_string());
+        self
+    }
+
+    /// Authenticates the client with a session returned by `auth.getSession` or
+    /// `auth.getMobileSession`.
+    pub fn set_session(&mut self, session: &Session) -> &mut Self {
+        self.set_sk(session.key.clone())
+    }
 
-        format!("{:x}", digest)
+    pub fn sign_api(&self, params: &mut HashMap<String, String>) -> Result<String> {
+        Ok(signature(params, &self.get_api_secret()?))
     }
 
     async fn send_http_request(
@@ -126,17 +255,20 @@ impl Lastfm {
         params: &mut HashMap<String, String>,
         http_method: Method,
     ) -> Result<Value> {
-        let url = self.get_base_url();
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
 
-        let client = self.get_client();
-        let response = match http_method {
-            Method::GET => client.get(url).query(&params).send().await?,
-            Method::POST => client.post(url).form(&params).send().await?,
-            _ => return Err(Error::Generic("Unsupported HTTP method".to_string())),
-        };
+        let response = self
+            .transport
+            .send(self.get_base_url(), http_method, params)
+            .await?;
 
-        let json_response: Value = response.json().await?;
-        Ok(json_response)
+        match serde_json::from_str::<Value>(&response.body) {
+            Ok(json_response) => Ok(json_response),
+            Err(_) if response.status >= 500 => Err(Error::HttpStatus(response.status)),
+            Err(err) => Err(err.into()),
+        }
     }
 
     // This function processes the response and returns either Success or Error
@@ -165,65 +297,132 @@ impl Lastfm {
         params.insert("method".to_string(), method.clone().into());
         params.insert("api_key".to_string(), self.get_api_key());
         if method.requires_auth() {
-            params.insert("sk".to_string(), self.get_sk());
-            let api_sig = self.sign_api(params);
+            params.insert("sk".to_string(), self.get_sk()?);
+        }
+        if method.requires_signature() {
+            let api_sig = self.sign_api(params)?;
             params.insert("api_sig".to_string(), api_sig);
         }
         params.insert("format".to_string(), "json".to_string());
 
-        let json_response = self.send_http_request(params, http_method).await?;
-        self.process_response(json_response).await
+        let is_write = method.requires_auth() || http_method == Method::POST;
+        let max_attempts = if is_write && !self.retry_policy.retry_writes {
+            1
+        } else {
+            self.retry_policy.max_attempts
+        };
+
+        // Responses are cached only for methods with a TTL, keyed by the final parameters.
+        let cache = self.cache.as_ref().and_then(|cache| {
+            let ttl = cache.ttl_for(&method)?;
+            Some((cache, ResponseCache::key(params), ttl))
+        });
+
+        if let Some((cache, key, _)) = &cache {
+            if let Some(json_response) = cache.get(key) {
+                return self.process_response(json_response).await;
+            }
+        }
+
+        let mut attempt = 1;
+        loop {
+            let outcome = match self.send_http_request(params, http_method.clone()).await {
+                Ok(json_response) => {
+                    if let Some((cache, key, ttl)) = &cache {
+                        if json_response.get("error").is_none() {
+                            cache.insert(key.clone(), json_response.clone(), *ttl);
+                        }
+                    }
+                    self.process_response(json_response).await
+                }
+                Err(err) => Err(err),
+            };
+
+            if attempt >= max_attempts || !self.retry_policy.is_retryable(&outcome) {
+                return outcome;
+            }
+
+            sleep(self.retry_policy.delay(attempt)).await;
+            attempt += 1;
+        }
     }
 
     /// Creates a new `Album` instance for interacting with album-related methods.
-    pub fn album(&self) -> Album {
+    pub fn album(&self) -> Album<'_> {
         Album::new(self)
     }
 
     /// Creates a new `Artist` instance for interacting with artist-related methods.
-    pub fn artist(&self) -> Artist {
+    pub fn artist(&self) -> Artist<'_> {
         Artist::new(self)
     }
 
     /// Creates a new `Auth` instance for interacting with auth-related methods.
-    pub fn auth(&self) -> Auth {
+    pub fn auth(&self) -> Auth<'_> {
         Auth::new(self)
     }
 
     /// Creates a new `Chart` instance for interacting with chart-related methods.
-    pub fn chart(&self) -> Chart {
+    pub fn chart(&self) -> Chart<'_> {
         Chart::new(self)
     }
 
     /// Creates a new `Geo` instance for interacting with geo-related methods.
-    pub fn geo(&self) -> Geo {
+    pub fn geo(&self) -> Geo<'_> {
         Geo::new(self)
     }
 
     /// Creates a new `Library` instance for interacting with library-related methods.
-    pub fn library(&self) -> Library {
+    pub fn library(&self) -> Library<'_> {
         Library::new(self)
     }
 
     /// Creates a new `Tag` instance for interacting with tag-related methods.
-    pub fn tag(&self) -> Tag {
+    pub fn tag(&self) -> Tag<'_> {
         Tag::new(self)
     }
 
     /// Creates a new `Track` instance for interacting with track-related methods.
-    pub fn track(&self) -> Track {
+    pub fn track(&self) -> Track<'_> {
         Track::new(self)
     }
 
     /// Creates a new `User` instance for interacting with user-related methods.
-    pub fn user(&self) -> User {
+    pub fn user(&self) -> User<'_> {
         User::new(self)
     }
 }
 
+/// Computes the `api_sig` of `params`: the MD5 of every key and value in key order, followed
+/// by the API secret.
+pub(crate) fn signature(params: &HashMap<String, String>, api_secret: &str) -> String {
+    let mut sorted_keys: Vec<String> = params.keys().cloned().collect();
+    sorted_keys.sort();
+    let mut concatenated_string = String::new();
+
+    for key in sorted_keys {
+        if let Some(value) = params.get(&key) {
+            concatenated_string.push_str(&key);
+            concatenated_string.push_str(value);
+        }
+    }
+    concatenated_string.push_str(api_secret);
+
+    // println!(
+    //     "Concatenated string for API signature: {}",
+    //     concatenated_string
+    // );
+
+    let string_bytes = concatenated_string.as_bytes();
+    let digest = md5::compute(string_bytes);
+
+    format!("{:x}", digest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::MemorySessionStore;
     use reqwest::Client;
     use std::collections::HashMap;
 
@@ -244,29 +443,99 @@ mod tests {
         assert_eq!(lastfm.get_api_key(), "test_api_key");
     }
 
+    #[test]
+    fn test_default_and_builder_do_not_retry() {
+        assert_eq!(Lastfm::default().get_retry_policy().max_attempts, 1);
+        assert_eq!(get_lastfm_instance().get_retry_policy().max_attempts, 1);
+    }
+
     #[tokio::test]
     async fn test_get_api_secret() {
         let lastfm = get_lastfm_instance();
-        assert_eq!(lastfm.get_api_secret(), "test_api_secret");
+        assert_eq!(lastfm.get_api_secret().unwrap(), "test_api_secret");
     }
 
     #[tokio::test]
     async fn test_get_base_url() {
         let lastfm = get_lastfm_instance();
-        assert_eq!(lastfm.get_base_url(), "http://ws.audioscrobbler.com/2.0/");
+        assert_eq!(lastfm.get_base_url(), "https://ws.audioscrobbler.com/2.0/");
+    }
+
+    #[tokio::test]
+    async fn test_librefm_preset() {
+        let lastfm = Lastfm::librefm()
+            .api_key("test_api_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(lastfm.get_base_url(), LIBREFM_API_URL);
+        assert_eq!(lastfm.get_auth_url(), LIBREFM_AUTH_URL);
     }
 
     #[tokio::test]
     async fn test_get_sk() {
         let lastfm = get_lastfm_instance();
-        assert_eq!(lastfm.get_sk(), "test_session_key");
+        assert_eq!(lastfm.get_sk().unwrap(), "test_session_key");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_client() {
+        let lastfm = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .build()
+            .unwrap();
+        assert!(matches!(lastfm.get_sk(), Err(Error::NotAuthenticated)));
+        assert!(matches!(
+            lastfm.get_api_secret(),
+            Err(Error::MissingCredential(_))
+        ));
+
+        let result = lastfm
+            .track()
+            .love()
+            .artist("Cher")
+            .track("Believe")
+            .send()
+            .await;
+        assert!(matches!(result, Err(Error::NotAuthenticated)));
+    }
+
+    #[tokio::test]
+    async fn test_build_requires_api_key() {
+        let result = Lastfm::builder()
+            .api_secret("test_api_secret".to_string())
+            .build();
+        assert!(matches!(result, Err(Error::MissingCredential(_))));
     }
 
     #[tokio::test]
     async fn test_set_sk() {
         let mut lastfm = get_lastfm_instance();
         lastfm.set_sk("new_session_key".to_string());
-        assert_eq!(lastfm.get_sk(), "new_session_key");
+        assert_eq!(lastfm.get_sk().unwrap(), "new_session_key");
+    }
+
+    #[tokio::test]
+    async fn test_session_store_loads_session_at_build() {
+        let store = Arc::new(MemorySessionStore::new());
+        store
+            .save(
+                "test_api_key",
+                &Session {
+                    name: "uppercase_".to_string(),
+                    key: "stored_session_key".to_string(),
+                    subscriber: false,
+                },
+            )
+            .unwrap();
+
+        let lastfm = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .session_store(store, "uppercase_")
+            .build()
+            .unwrap();
+        assert_eq!(lastfm.get_sk().unwrap(), "stored_session_key");
     }
 
     #[tokio::test]
@@ -275,7 +544,7 @@ mod tests {
         let mut params = HashMap::new();
         params.insert("method".to_string(), "test_method".to_string());
         params.insert("api_key".to_string(), lastfm.get_api_key());
-        let signature = lastfm.sign_api(&mut params);
+        let signature = lastfm.sign_api(&mut params).unwrap();
         assert!(!signature.is_empty());
     }
 }
@@ -1,9 +1,34 @@
 mod api;
+mod cache;
+mod cassette;
 mod error;
 mod lastfm;
+#[cfg(any(test, feature = "test-util"))]
+mod mock_server;
 mod models;
+mod rate_limit;
+mod retry;
+mod scrobble_queue;
+mod session_store;
+mod transport;
 
-pub use api::{Album, Artist, Auth};
-pub use error::{ApiError, Error, Result};
-pub use lastfm::{Lastfm, LASTFM_API_URL};
+pub use api::{
+    Album, Artist, Auth, AuthStatus, BatchResult, LastfmMethod, Paginated, Period, ScrobbleEntry,
+    ScrobbleResult, DEFAULT_PAGE_CONCURRENCY, MAX_RANGE_WEEKS, MAX_SCROBBLES_PER_REQUEST,
+};
+pub use cache::{CacheBackend, LruCache, ResponseCache};
+pub use cassette::Cassette;
+pub use error::{ApiError, ApiErrorCode, Error, Result, ValidationError};
+pub use lastfm::{
+    Lastfm, LastfmBuilder, LASTFM_API_URL, LASTFM_AUTH_URL, LIBREFM_API_URL, LIBREFM_AUTH_URL,
+};
+#[cfg(any(test, feature = "test-util"))]
+pub use mock_server::MockServer;
 pub use models::*;
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use scrobble_queue::{FlushReport, FlushStop, ScrobbleQueue, SCROBBLE_MAX_AGE};
+pub use session_store::{FileSessionStore, MemorySessionStore, SessionStore};
+pub use transport::{
+    MockTransport, ReqwestTransport, Transport, TransportFuture, TransportResponse,
+};
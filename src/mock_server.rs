@@ -0,0 +1,664 @@
+//! A local stand-in for the Last.fm API, for end-to-end tests.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use reqwest::Url;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::lastfm::signature;
+use crate::{ApiErrorCode, Lastfm, LastfmMethod, Result, ScrobbleEntry};
+
+/// Parameters that are not part of the `api_sig`.
+const UNSIGNED_PARAMS: [&str; 3] = ["api_sig", "format", "callback"];
+
+/// An HTTP server emulating `ws.audioscrobbler.com/2.0/` on a local port.
+///
+/// Every request must carry the server's API key and, for methods that require one, a valid
+/// `api_sig`. The auth token and session exchange is emulated, scrobbles are kept in memory
+/// and served back by `user.getRecentTracks`, and any other method returns the response set
+/// with [`MockServer::respond`]. The server stops when it is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    api_key: String,
+    api_secret: String,
+    next_id: u64,
+    /// Issued tokens, with the user who authorized them.
+    tokens: HashMap<String, Option<String>>,
+    passwords: HashMap<String, String>,
+    /// Session keys, with the user they belong to.
+    sessions: HashMap<String, String>,
+    scrobbles: HashMap<String, Vec<ScrobbleEntry>>,
+    now_playing: HashMap<String, ScrobbleEntry>,
+    responses: HashMap<String, Value>,
+}
+
+impl MockServer {
+    /// Starts a server on a random local port that accepts `api_key` and `api_secret`.
+    pub async fn start(api_key: &str, api_secret: &str) -> Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            ..Default::default()
+        }));
+
+        let task = tokio::spawn(serve(listener, state.clone()));
+
+        Ok(MockServer { addr, state, task })
+    }
+
+    /// The URL to pass to [`LastfmBuilder::base_url`](crate::LastfmBuilder::base_url).
+    pub fn url(&self) -> String {
+        format!("http://{}/2.0/", self.addr)
+    }
+
+    /// A client pointed at this server with its API key and secret.
+    pub fn lastfm(&self) -> Result<Lastfm> {
+        let state = self.state();
+        Lastfm::builder()
+            .api_key(state.api_key.clone())
+            .api_secret(state.api_secret.clone())
+            .base_url(&self.url())
+            .build()
+    }
+
+    /// Registers a user that can sign in through `auth.getMobileSession`.
+    pub fn add_user(&self, username: &str, password: &str) {
+        self.state()
+            .passwords
+            .insert(username.to_string(), password.to_string());
+    }
+
+    /// Marks `token` as approved by `username`, as if they had visited the authorization page.
+    ///
+    /// Returns `false` if the token was never issued by `auth.getToken`.
+    pub fn authorize_token(&self, token: &str, username: &str) -> bool {
+        match self.state().tokens.get_mut(token) {
+            Some(authorized) => {
+                *authorized = Some(username.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Creates a session for `username` and returns its key.
+    pub fn create_session(&self, username: &str) -> String {
+        self.state().create_session(username)
+    }
+
+    /// The scrobbles accepted for `username`, in the order they were received.
+    pub fn scrobbles(&self, username: &str) -> Vec<ScrobbleEntry> {
+        self.state()
+            .scrobbles
+            .get(username)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Answers every call to `method` with `body`.
+    pub fn respond(&self, method: LastfmMethod, body: Value) {
+        self.state().responses.insert(method.to_string(), body);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+async fn serve(listener: TcpListener, state: Arc<Mutex<State>>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_connection(stream, state.clone()));
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<State>>) -> Result<()> {
+    let params = match read_params(&mut stream).await? {
+        Some(params) => params,
+        None => return respond(&mut stream, 400, &Value::Null).await,
+    };
+
+    let (status, body) = lock(&state).handle(&params);
+    respond(&mut stream, status, &body).await
+}
+
+/// Reads a request and returns its query and form parameters.
+async fn read_params(stream: &mut TcpStream) -> Result<Option<HashMap<String, String>>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+
+    let head_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buffer.len() < head_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    let target = match head.lines().next().and_then(|line| line.split(' ').nth(1)) {
+        Some(target) => target,
+        None => return Ok(None),
+    };
+    let url = match Url::parse(&format!("http://localhost{}", target)) {
+        Ok(url) => url,
+        Err(_) => return Ok(None),
+    };
+
+    let mut params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let body = String::from_utf8_lossy(&buffer[head_end..]).into_owned();
+    if let Ok(form) = Url::parse(&format!("http://localhost/?{}", body)) {
+        params.extend(form.query_pairs().into_owned());
+    }
+
+    Ok(Some(params))
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, body: &Value) -> Result<()> {
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        if status == 200 { "OK" } else { "Error" },
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn api_error(status: u16, code: ApiErrorCode, message: &str) -> (u16, Value) {
+    (status, json!({ "error": code.code(), "message": message }))
+}
+
+fn corrected(text: Option<&str>) -> Value {
+    match text {
+        Some(text) => json!({ "corrected": "0", "#text": text }),
+        None => json!({ "corrected": "0" }),
+    }
+}
+
+impl State {
+    fn handle(&mut self, params: &HashMap<String, String>) -> (u16, Value) {
+        if params.get("api_key") != Some(&self.api_key) {
+            return api_error(
+                403,
+                ApiErrorCode::InvalidApiKey,
+                "Invalid API key - You must be granted a valid key by last.fm",
+            );
+        }
+
+        let name = params.get("method").map(String::as_str).unwrap_or_default();
+        let method: LastfmMethod = match name.parse() {
+            Ok(method) => method,
+            Err(_) => {
+                return api_error(
+                    400,
+                    ApiErrorCode::InvalidMethod,
+                    "Invalid Method - No method with that name in this package",
+                )
+            }
+        };
+
+        if method.requires_signature() || params.contains_key("api_sig") {
+            let signed: HashMap<String, String> = params
+                .iter()
+                .filter(|(key, _)| !UNSIGNED_PARAMS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+
+            if params.get("api_sig") != Some(&signature(&signed, &self.api_secret)) {
+                return api_error(
+                    403,
+                    ApiErrorCode::InvalidSignature,
+                    "Invalid method signature supplied",
+                );
+            }
+        }
+
+        let user = if method.requires_auth() {
+            match params.get("sk").and_then(|sk| self.sessions.get(sk)) {
+                Some(user) => Some(user.clone()),
+                None => {
+                    return api_error(
+                        403,
+                        ApiErrorCode::InvalidSessionKey,
+                        "Invalid session key - Please re-authenticate",
+                    )
+                }
+            }
+        } else {
+            None
+        };
+
+        match (method, user) {
+            (LastfmMethod::AuthGetToken, _) => self.get_token(),
+            (LastfmMethod::AuthGetSession, _) => self.get_session(params),
+            (LastfmMethod::AuthGetMobileSession, _) => self.get_mobile_session(params),
+            (LastfmMethod::TrackScrobble, Some(user)) => self.scrobble(&user, params),
+            (LastfmMethod::TrackUpdateNowPlaying, Some(user)) => {
+                self.update_now_playing(&user, params)
+            }
+            (LastfmMethod::UserGetRecentTracks, _) => self.get_recent_tracks(params),
+            (method, _) => match self.responses.get(&method.to_string()) {
+                Some(body) => (200, body.clone()),
+                None => api_error(
+                    501,
+                    ApiErrorCode::InvalidMethod,
+                    "Invalid Method - Not emulated by the mock server",
+                ),
+            },
+        }
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}{:08}", prefix, self.next_id)
+    }
+
+    fn create_session(&mut self, username: &str) -> String {
+        let key = self.next_id("session");
+        self.sessions.insert(key.clone(), username.to_string());
+        key
+    }
+
+    fn session(&mut self, username: &str) -> (u16, Value) {
+        let key = self.create_session(username);
+        (
+            200,
+            json!({ "session": { "name": username, "key": key, "subscriber": 0 } }),
+        )
+    }
+
+    fn get_token(&mut self) -> (u16, Value) {
+        let token = self.next_id("token");
+        self.tokens.insert(token.clone(), None);
+        (200, json!({ "token": token }))
+    }
+
+    fn get_session(&mut self, params: &HashMap<String, String>) -> (u16, Value) {
+        let token = params.get("token").map(String::as_str).unwrap_or_default();
+
+        match self.tokens.get(token).cloned() {
+            Some(Some(username)) => {
+                self.tokens.remove(token);
+                self.session(&username)
+            }
+            Some(None) => api_error(
+                403,
+                ApiErrorCode::UnauthorizedToken,
+                "Unauthorized Token - This token has not been authorized",
+            ),
+            None => api_error(
+                403,
+                ApiErrorCode::AuthenticationFailed,
+                "Invalid authentication token supplied",
+            ),
+        }
+    }
+
+    fn get_mobile_session(&mut self, params: &HashMap<String, String>) -> (u16, Value) {
+        let username = params.get("username").cloned().unwrap_or_default();
+
+        if params.get("password").is_some()
+            && self.passwords.get(&username) == params.get("password")
+        {
+            self.session(&username)
+        } else {
+            api_error(
+                403,
+                ApiErrorCode::AuthenticationFailed,
+                "Authentication Failed - You do not have permissions to access the service",
+            )
+        }
+    }
+
+    fn scrobble(&mut self, user: &str, params: &HashMap<String, String>) -> (u16, Value) {
+        let mut entries = Vec::new();
+
+        for index in 0.. {
+            let key = |name: &str| format!("{}[{}]", name, index);
+            let indexed = params.contains_key(&key("artist"));
+            if !indexed && (index > 0 || !params.contains_key("artist")) {
+                break;
+            }
+
+            let get = |name: &str| {
+                if indexed {
+                    params.get(&key(name)).cloned()
+                } else {
+                    params.get(name).cloned()
+                }
+            };
+
+            let (artist, track, timestamp) = match (
+                get("artist"),
+                get("track"),
+                get("timestamp").and_then(|ts| ts.parse().ok()),
+            ) {
+                (Some(artist), Some(track), Some(timestamp)) => (artist, track, timestamp),
+                _ => {
+                    return api_error(
+                        400,
+                        ApiErrorCode::InvalidParameters,
+                        "Invalid parameters - artist, track and timestamp are required",
+                    )
+                }
+            };
+
+            let mut entry = ScrobbleEntry::new(artist, track, timestamp);
+            entry.album = get("album");
+            entry.album_artist = get("albumArtist");
+            entries.push(entry);
+
+            if !indexed {
+                break;
+            }
+        }
+
+        if entries.is_empty() {
+            return api_error(
+                400,
+                ApiErrorCode::InvalidParameters,
+                "Invalid parameters - no scrobbles supplied",
+            );
+        }
+
+        let scrobbles: Vec<Value> = entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "artist": corrected(Some(&entry.artist)),
+                    "track": corrected(Some(&entry.track)),
+                    "album": corrected(entry.album.as_deref()),
+                    "albumArtist": corrected(entry.album_artist.as_deref()),
+                    "timestamp": entry.timestamp.to_string(),
+                    "ignoredMessage": { "code": "0", "#text": "" },
+                })
+            })
+            .collect();
+        let accepted = entries.len();
+
+        self.now_playing.remove(user);
+        self.scrobbles
+            .entry(user.to_string())
+            .or_default()
+            .extend(entries);
+
+        (
+            200,
+            json!({
+                "scrobbles": {
+                    "scrobble": scrobbles,
+                    "@attr": { "accepted": accepted, "ignored": 0 },
+                }
+            }),
+        )
+    }
+
+    fn update_now_playing(&mut self, user: &str, params: &HashMap<String, String>) -> (u16, Value) {
+        let (artist, track) = match (params.get("artist"), params.get("track")) {
+            (Some(artist), Some(track)) => (artist, track),
+            _ => {
+                return api_error(
+                    400,
+                    ApiErrorCode::InvalidParameters,
+                    "Invalid parameters - artist and track are required",
+                )
+            }
+        };
+
+        let mut entry = ScrobbleEntry::new(artist.as_str(), track.as_str(), 0);
+        entry.album = params.get("album").cloned();
+        entry.album_artist = params.get("albumArtist").cloned();
+
+        let body = json!({
+            "nowplaying": {
+                "artist": corrected(Some(&entry.artist)),
+                "track": corrected(Some(&entry.track)),
+                "album": corrected(entry.album.as_deref()),
+                "albumArtist": corrected(entry.album_artist.as_deref()),
+                "ignoredMessage": { "code": "0", "#text": "" },
+            }
+        });
+        self.now_playing.insert(user.to_string(), entry);
+
+        (200, body)
+    }
+
+    fn get_recent_tracks(&self, params: &HashMap<String, String>) -> (u16, Value) {
+        let user = match params.get("user") {
+            Some(user) => user,
+            None => {
+                return api_error(
+                    400,
+                    ApiErrorCode::InvalidParameters,
+                    "Invalid parameters - user is required",
+                )
+            }
+        };
+        let known = self.scrobbles.contains_key(user)
+            || self.passwords.contains_key(user)
+            || self.sessions.values().any(|name| name == user);
+        if !known {
+            return api_error(404, ApiErrorCode::InvalidParameters, "User not found");
+        }
+
+        let number = |name: &str, default: u64| {
+            params
+                .get(name)
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        let limit = number("limit", 50).clamp(1, 200) as usize;
+        let page = number("page", 1).max(1) as usize;
+        let from = number("from", 0);
+        let to = number("to", u64::MAX);
+
+        let mut played: Vec<&ScrobbleEntry> = self
+            .scrobbles
+            .get(user)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.timestamp >= from && entry.timestamp < to)
+            .collect();
+        played.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+
+        let total = played.len();
+        let total_pages = total.div_ceil(limit).max(1);
+
+        let mut tracks: Vec<Value> = Vec::new();
+        if let Some(entry) = self.now_playing.get(user) {
+            let mut track = recent_track(entry);
+            track["@attr"] = json!({ "nowplaying": "true" });
+            tracks.push(track);
+        }
+        tracks.extend(
+            played
+                .into_iter()
+                .skip((page - 1) * limit)
+                .take(limit)
+                .map(|entry| {
+                    let mut track = recent_track(entry);
+                    track["date"] = json!({ "uts": entry.timestamp.to_string(), "#text": "" });
+                    track
+                }),
+        );
+
+        (
+            200,
+            json!({
+                "recenttracks": {
+                    "track": tracks,
+                    "@attr": {
+                        "user": user,
+                        "page": page.to_string(),
+                        "perPage": limit.to_string(),
+                        "totalPages": total_pages.to_string(),
+                        "total": total.to_string(),
+                    }
+                }
+            }),
+        )
+    }
+}
+
+fn recent_track(entry: &ScrobbleEntry) -> Value {
+    json!({
+        "artist": { "mbid": "", "#text": entry.artist },
+        "streamable": "0",
+        "image": [],
+        "mbid": entry.mbid.clone().unwrap_or_default(),
+        "album": { "mbid": "", "#text": entry.album.clone().unwrap_or_default() },
+        "name": entry.track,
+        "url": "",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{APIResponse, AuthStatus};
+
+    #[tokio::test]
+    async fn test_web_auth_and_scrobble_round_trip() {
+        let server = MockServer::start("test_api_key", "test_api_secret")
+            .await
+            .unwrap();
+        let mut lastfm = server.lastfm().unwrap();
+
+        let flow = lastfm.auth().start_web_auth().await.unwrap();
+        assert_eq!(flow.poll().await.unwrap(), AuthStatus::Pending);
+        assert!(server.authorize_token(flow.token(), "uppercase_"));
+        let session = match flow.poll().await.unwrap() {
+            AuthStatus::Approved(session) => session,
+            status => panic!("expected an approved session, got {:?}", status),
+        };
+        assert_eq!(session.name, "uppercase_");
+        lastfm.set_session(&session);
+
+        let entries = vec![
+            ScrobbleEntry::new("Cher", "Believe", 1_700_000_000),
+            ScrobbleEntry::new("ABBA", "Dancing Queen", 1_700_000_300),
+        ];
+        let response = lastfm
+            .track()
+            .scrobble_batch()
+            .entries(entries.clone())
+            .send()
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert!(response.iter().all(|r| r.accepted));
+        assert_eq!(server.scrobbles("uppercase_"), entries);
+
+        let recent = lastfm
+            .user()
+            .get_recent_tracks()
+            .username("uppercase_")
+            .send()
+            .await
+            .unwrap();
+        match recent {
+            APIResponse::Success(recent) => {
+                let tracks = recent.recenttracks.tracks;
+                assert_eq!(tracks[0].name, "Dancing Queen");
+                assert_eq!(tracks[0].played_at, Some(1_700_000_300));
+                assert_eq!(tracks[1].name, "Believe");
+            }
+            APIResponse::Error(err) => panic!("unexpected API error: {}", err.message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_signature_and_api_key() {
+        let server = MockServer::start("test_api_key", "test_api_secret")
+            .await
+            .unwrap();
+        server.add_user("uppercase_", "hunter2");
+
+        let wrong_secret = Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .api_secret("wrong_secret".to_string())
+            .base_url(&server.url())
+            .build()
+            .unwrap();
+        let response = wrong_secret
+            .auth()
+            .get_mobile_session()
+            .username("uppercase_")
+            .password("hunter2")
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            matches!(response, APIResponse::Error(err) if err.code() == ApiErrorCode::InvalidSignature)
+        );
+
+        let wrong_key = Lastfm::builder()
+            .api_key("wrong_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .base_url(&server.url())
+            .build()
+            .unwrap();
+        let response = wrong_key.auth().get_token().send().await.unwrap();
+        assert!(
+            matches!(response, APIResponse::Error(err) if err.code() == ApiErrorCode::InvalidApiKey)
+        );
+
+        let response = server
+            .lastfm()
+            .unwrap()
+            .auth()
+            .get_mobile_session()
+            .username("uppercase_")
+            .password("hunter2")
+            .send()
+            .await
+            .unwrap();
+        assert!(matches!(response, APIResponse::Success(_)));
+    }
+}
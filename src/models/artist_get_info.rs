@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::{ArtistSummary, Image, TagList, Wiki};
+use super::de::{bool_from_str, one_or_many, option_u64_from_str, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistGetInfoResponse {
+    pub artist: ArtistInfo,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistInfo {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+    #[serde(default, deserialize_with = "bool_from_str")]
+    pub ontour: bool,
+    #[serde(default)]
+    pub stats: ArtistStats,
+    #[serde(default)]
+    pub similar: SimilarArtistList,
+    #[serde(default)]
+    pub tags: TagList,
+    #[serde(default)]
+    pub bio: Wiki,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistStats {
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub listeners: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub playcount: u64,
+    /// Only reported when the request names a `username`.
+    #[serde(default, deserialize_with = "option_u64_from_str")]
+    pub userplaycount: Option<u64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarArtistList {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub artist: Vec<ArtistSummary>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artist_info_response() {
+        let json = r##"{"artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818",
+            "url":"https://www.last.fm/music/Cher",
+            "image":[{"#text":"","size":"small"},{"#text":"","size":"medium"}],
+            "streamable":"0","ontour":"0",
+            "stats":{"listeners":"1531145","playcount":"26435380","userplaycount":"12"},
+            "similar":{"artist":[{"name":"Madonna","url":"https://www.last.fm/music/Madonna","image":[]}]},
+            "tags":{"tag":[{"name":"pop","url":"https://www.last.fm/tag/pop"}]},
+            "bio":{"links":{"link":{"#text":"","rel":"original","href":"https://last.fm/music/Cher/+wiki"}},
+                "published":"01 Jan 2006, 00:00","summary":"Cher is a singer.","content":"Cher is a singer and actress."}}}"##;
+        let response: ArtistGetInfoResponse = serde_json::from_str(json).unwrap();
+        let artist = response.artist;
+
+        assert_eq!(artist.name, "Cher");
+        assert!(!artist.ontour);
+        assert_eq!(artist.stats.listeners, 1_531_145);
+        assert_eq!(artist.stats.playcount, 26_435_380);
+        assert_eq!(artist.stats.userplaycount, Some(12));
+        assert_eq!(artist.similar.artist[0].name, "Madonna");
+        assert_eq!(artist.tags.tag[0].name, "pop");
+        assert_eq!(artist.bio.published, "01 Jan 2006, 00:00");
+        assert_eq!(artist.bio.summary, "Cher is a singer.");
+    }
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Image;
+use super::de::{f64_from_str, one_or_many};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistGetSimilarResponse {
+    pub similarartists: SimilarArtists,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarArtists {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub artist: Vec<SimilarArtist>,
+    #[serde(rename = "@attr", default)]
+    pub attr: SimilarArtistsAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarArtistsAttr {
+    /// The artist the others are similar to.
+    pub artist: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarArtist {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    /// How similar the artist is, from 0 to 1.
+    #[serde(rename = "match", default, deserialize_with = "f64_from_str")]
+    pub match_score: f64,
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_artists_response() {
+        let json = r##"{"similarartists":{"artist":[
+            {"name":"Madonna","mbid":"79239441-bfd5-4981-a70c-55c3f15c1287","match":"1",
+             "url":"https://www.last.fm/music/Madonna","image":[{"#text":"","size":"small"}],"streamable":"0"},
+            {"name":"Kylie Minogue","match":"0.834712","url":"https://www.last.fm/music/Kylie+Minogue","image":[],"streamable":"0"}
+        ],"@attr":{"artist":"Cher"}}}"##;
+        let response: ArtistGetSimilarResponse = serde_json::from_str(json).unwrap();
+        let similar = response.similarartists;
+
+        assert_eq!(similar.attr.artist, "Cher");
+        assert_eq!(similar.artist[0].match_score, 1.0);
+        assert_eq!(similar.artist[1].match_score, 0.834712);
+        assert_eq!(similar.artist[1].mbid, "");
+    }
+}
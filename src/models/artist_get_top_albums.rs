@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::{fill_ranks, ArtistSummary, Image, PageInfo};
+use super::de::{one_or_many, rank_from_attr, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistGetTopAlbumsResponse {
+    pub topalbums: ArtistTopAlbums,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ArtistTopAlbumsPayload")]
+pub struct ArtistTopAlbums {
+    pub album: Vec<ArtistTopAlbum>,
+    #[serde(rename = "@attr")]
+    pub attr: ArtistChartAttr,
+}
+
+/// The `@attr` block of the artist top-* charts.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistChartAttr {
+    #[serde(default)]
+    pub artist: String,
+    #[serde(flatten)]
+    pub page_info: PageInfo,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistTopAlbum {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub playcount: u64,
+    pub url: String,
+    pub artist: ArtistSummary,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+    /// The position in the chart, counted from the first page.
+    #[serde(rename = "@attr", default, deserialize_with = "rank_from_attr")]
+    pub rank: u64,
+}
+
+/// Albums are listed without a rank, so it is derived from their position.
+#[derive(Deserialize)]
+struct ArtistTopAlbumsPayload {
+    #[serde(default, deserialize_with = "one_or_many")]
+    album: Vec<ArtistTopAlbum>,
+    #[serde(rename = "@attr", default)]
+    attr: ArtistChartAttr,
+}
+
+impl From<ArtistTopAlbumsPayload> for ArtistTopAlbums {
+    fn from(payload: ArtistTopAlbumsPayload) -> Self {
+        let mut album = payload.album;
+        fill_ranks(&mut album, &payload.attr.page_info, |album| &mut album.rank);
+
+        ArtistTopAlbums {
+            album,
+            attr: payload.attr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_albums_response() {
+        let json = r##"{"topalbums":{"album":[
+            {"name":"Believe","playcount":1960566,"mbid":"63b3a8ca-26f2-4e2b-b867-647a6ec2bebd",
+             "url":"https://www.last.fm/music/Cher/Believe",
+             "artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818","url":"https://www.last.fm/music/Cher"},
+             "image":[{"#text":"","size":"small"}]},
+            {"name":"The Very Best of Cher","playcount":"1510426",
+             "url":"https://www.last.fm/music/Cher/The+Very+Best+of+Cher",
+             "artist":{"name":"Cher","mbid":"","url":"https://www.last.fm/music/Cher"},"image":[]}
+        ],"@attr":{"artist":"Cher","page":"2","perPage":"2","totalPages":"50","total":"100"}}}"##;
+        let response: ArtistGetTopAlbumsResponse = serde_json::from_str(json).unwrap();
+        let top = response.topalbums;
+
+        assert_eq!(top.attr.artist, "Cher");
+        assert_eq!(top.attr.page_info.total_pages, 50);
+        assert_eq!(top.album[0].playcount, 1_960_566);
+        assert_eq!(top.album[1].playcount, 1_510_426);
+        assert_eq!(top.album[0].rank, 3);
+        assert_eq!(top.album[1].rank, 4);
+        assert_eq!(top.album[0].artist.name, "Cher");
+    }
+}
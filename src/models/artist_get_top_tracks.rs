@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use super::artist_get_top_albums::ArtistChartAttr;
+use super::common::{ArtistSummary, Image};
+use super::de::{one_or_many, rank_from_attr, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistGetTopTracksResponse {
+    pub toptracks: ArtistTopTracks,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistTopTracks {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub track: Vec<ArtistTopTrack>,
+    #[serde(rename = "@attr", default)]
+    pub attr: ArtistChartAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistTopTrack {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub playcount: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub listeners: u64,
+    pub url: String,
+    pub artist: ArtistSummary,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+    /// The position in the chart, counted from the first page.
+    #[serde(rename = "@attr", default, deserialize_with = "rank_from_attr")]
+    pub rank: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_tracks_response() {
+        let json = r##"{"toptracks":{"track":{"name":"Believe","playcount":"8516093","listeners":"1098475",
+            "mbid":"32ca187e-ee25-4f18-b7d0-3b6713f24635","url":"https://www.last.fm/music/Cher/_/Believe",
+            "streamable":"0","artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818",
+            "url":"https://www.last.fm/music/Cher"},"image":[{"#text":"","size":"small"}],"@attr":{"rank":"1"}},
+            "@attr":{"artist":"Cher","page":"1","perPage":"1","totalPages":"5000","total":"5000"}}}"##;
+        let response: ArtistGetTopTracksResponse = serde_json::from_str(json).unwrap();
+        let top = response.toptracks;
+
+        assert_eq!(top.track.len(), 1);
+        assert_eq!(top.track[0].rank, 1);
+        assert_eq!(top.track[0].playcount, 8_516_093);
+        assert_eq!(top.track[0].listeners, 1_098_475);
+        assert_eq!(top.attr.page_info.total, 5000);
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::de::bool_from_str;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthGetSessionResponse {
+    pub session: Session,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthGetMobileSessionResponse {
+    pub session: Session,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub name: String,
+    pub key: String,
+    #[serde(default, deserialize_with = "bool_from_str")]
+    pub subscriber: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_response() {
+        let json = r#"{"session":{"subscriber":0,"name":"uppercase_","key":"d580d57f32848f5dcf574d1ce18d78b2"}}"#;
+        let response: AuthGetSessionResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.session.name, "uppercase_");
+        assert_eq!(response.session.key, "d580d57f32848f5dcf574d1ce18d78b2");
+        assert!(!response.session.subscriber);
+    }
+}
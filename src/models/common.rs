@@ -0,0 +1,181 @@
+//! Building blocks shared by the response models of several methods.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::de::{one_or_many, u64_from_str};
+
+/// A picture of an artist, album or track, in one of the sizes Last.fm serves.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Image {
+    #[serde(rename = "#text", default)]
+    pub url: String,
+    #[serde(default)]
+    pub size: String,
+}
+
+/// An artist as listed in another entity's response, e.g. the artist of an album.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtistSummary {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagList {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub tag: Vec<TagSummary>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagSummary {
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+}
+
+/// The biography of an artist, or the description of an album or track.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wiki {
+    #[serde(default)]
+    pub published: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub content: String,
+}
+
+/// The pagination metadata of a paged response.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub page: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub per_page: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub total_pages: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub total: u64,
+}
+
+impl PageInfo {
+    /// Reads the pagination metadata of a response, such as `{"topartists": {"@attr": {...}}}`.
+    ///
+    /// Most methods report it in an `@attr` block. Search methods use the `opensearch:*` keys
+    /// instead, from which the page number and page count are derived.
+    pub fn from_value(response: &Value) -> Option<PageInfo> {
+        let container = response.as_object()?.values().next()?;
+
+        if let Some(attr) = container
+            .get("@attr")
+            .filter(|attr| attr.get("totalPages").is_some())
+        {
+            return serde_json::from_value(attr.clone()).ok();
+        }
+
+        let number = |key: &str| -> Option<u64> {
+            match container.get(key)? {
+                Value::String(value) => value.parse().ok(),
+                value => value.as_u64(),
+            }
+        };
+        Some(PageInfo::from_opensearch(
+            number("opensearch:totalResults")?,
+            number("opensearch:startIndex").unwrap_or(0),
+            number("opensearch:itemsPerPage")?,
+        ))
+    }
+
+    /// Derives the pagination of a search page from its `opensearch:*` counts.
+    pub(crate) fn from_opensearch(total: u64, start_index: u64, items_per_page: u64) -> PageInfo {
+        let per_page = items_per_page.max(1);
+
+        PageInfo {
+            page: start_index / per_page + 1,
+            per_page,
+            total_pages: total.div_ceil(per_page),
+            total,
+        }
+    }
+
+    /// Whether there is a page after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages
+    }
+}
+
+/// Numbers the entries of a chart page that Last.fm sent without a rank.
+pub(crate) fn fill_ranks<T>(
+    items: &mut [T],
+    page_info: &PageInfo,
+    rank: impl Fn(&mut T) -> &mut u64,
+) {
+    let offset = page_info.page.saturating_sub(1) * page_info.per_page;
+    for (position, item) in (1..).zip(items.iter_mut()) {
+        let rank = rank(item);
+        if *rank == 0 {
+            *rank = offset + position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_page_info_from_attr() {
+        let response = json!({
+            "topartists": {
+                "artist": [],
+                "@attr": { "page": "2", "perPage": "50", "totalPages": "7", "total": "330" }
+            }
+        });
+
+        let page_info = PageInfo::from_value(&response).unwrap();
+        assert_eq!(
+            page_info,
+            PageInfo {
+                page: 2,
+                per_page: 50,
+                total_pages: 7,
+                total: 330
+            }
+        );
+        assert!(page_info.has_next_page());
+    }
+
+    #[test]
+    fn test_page_info_from_opensearch() {
+        let response = json!({
+            "results": {
+                "opensearch:Query": { "#text": "", "role": "request", "startPage": "3" },
+                "opensearch:totalResults": "95",
+                "opensearch:startIndex": "60",
+                "opensearch:itemsPerPage": "30",
+                "artistmatches": { "artist": [] },
+                "@attr": { "for": "cher" }
+            }
+        });
+
+        let page_info = PageInfo::from_value(&response).unwrap();
+        assert_eq!(page_info.page, 3);
+        assert_eq!(page_info.total_pages, 4);
+        assert_eq!(page_info.total, 95);
+    }
+
+    #[test]
+    fn test_page_info_missing() {
+        assert_eq!(
+            PageInfo::from_value(&json!({ "tag": { "name": "disco" } })),
+            None
+        );
+    }
+}
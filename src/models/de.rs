@@ -0,0 +1,150 @@
+//! Deserialization helpers for the quirks of Last.fm's JSON output.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer};
+
+/// Last.fm renders a list with a single element as a bare object instead of an array.
+///
+/// `Many` is tried first: a struct whose fields all have defaults would otherwise also accept
+/// an empty array as a single item.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+/// Deserializes a value that may be either a single item or an array of items.
+pub(crate) fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(match Option::<OneOrMany<T>>::deserialize(deserializer)? {
+        Some(OneOrMany::One(item)) => vec![item],
+        Some(OneOrMany::Many(items)) => items,
+        None => Vec::new(),
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    Number(u64),
+    String(String),
+}
+
+/// Deserializes an unsigned integer that Last.fm may send either as a number or as a string.
+pub(crate) fn u64_from_str<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::Number(n) => Ok(n),
+        StringOrNumber::String(s) if s.is_empty() => Ok(0),
+        StringOrNumber::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Flag {
+    Bool(bool),
+    Number(u64),
+    String(String),
+}
+
+/// Deserializes a `"0"`/`"1"` flag (or the numeric or boolean equivalent) into a `bool`.
+pub(crate) fn bool_from_str<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Flag::deserialize(deserializer)? {
+        Flag::Bool(b) => Ok(b),
+        Flag::Number(n) => Ok(n != 0),
+        Flag::String(s) => Ok(!(s.is_empty() || s == "0" || s == "false")),
+    }
+}
+
+/// Like [`bool_from_str`], for a flag that is only present in some responses.
+pub(crate) fn option_bool_from_str<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    bool_from_str(deserializer).map(Some)
+}
+
+/// Like [`u64_from_str`], for a count that is only present in some responses.
+pub(crate) fn option_u64_from_str<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    u64_from_str(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrFloat {
+    Number(f64),
+    String(String),
+}
+
+/// Deserializes a float such as a similarity `match` that Last.fm may send as a string.
+pub(crate) fn f64_from_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StringOrFloat::deserialize(deserializer)? {
+        StringOrFloat::Number(n) => Ok(n),
+        StringOrFloat::String(s) if s.is_empty() => Ok(0.0),
+        StringOrFloat::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Rank {
+    Attr {
+        #[serde(deserialize_with = "u64_from_str")]
+        rank: u64,
+    },
+    Rank(#[serde(deserialize_with = "u64_from_str")] u64),
+}
+
+/// Deserializes the position of a chart entry from its `"@attr": {"rank": "1"}` block.
+pub(crate) fn rank_from_attr<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Rank::deserialize(deserializer)? {
+        Rank::Attr { rank } | Rank::Rank(rank) => Ok(rank),
+    }
+}
+
+fn duration<'de, D>(
+    deserializer: D,
+    unit: fn(u64) -> Duration,
+) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let amount = u64_from_str(deserializer)?;
+    Ok((amount > 0).then(|| unit(amount)))
+}
+
+/// Deserializes a duration in milliseconds, where `0` stands for an unknown duration.
+pub(crate) fn duration_from_millis<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    duration(deserializer, Duration::from_millis)
+}
+
+/// Deserializes a duration in seconds, where `0` stands for an unknown duration.
+pub(crate) fn duration_from_secs<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    duration(deserializer, Duration::from_secs)
+}
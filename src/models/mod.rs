@@ -1,15 +1,56 @@
 mod album_get_info_response;
 mod album_get_tags;
 mod album_get_top_tags;
+mod artist_get_info;
+mod artist_get_similar;
+mod artist_get_top_albums;
+mod artist_get_top_tracks;
 mod auth_get_token;
+mod auth_session;
+mod common;
+pub(crate) mod de;
+mod search;
+mod track_get_correction;
+mod track_get_info;
+mod track_get_similar;
+mod track_scrobble;
+mod user_get_recent_tracks;
 
 use std::fmt;
 
 pub use album_get_info_response::AlbumGetInfoResponse;
 pub use album_get_tags::AlbumGetTagsResponse;
-pub use auth_get_token::AuthGetTokenResponse;
 pub use album_get_top_tags::AlbumGetTopTagsResponse;
+pub use artist_get_info::{ArtistGetInfoResponse, ArtistInfo, ArtistStats, SimilarArtistList};
+pub use artist_get_similar::{
+    ArtistGetSimilarResponse, SimilarArtist, SimilarArtists, SimilarArtistsAttr,
+};
+pub use artist_get_top_albums::{
+    ArtistChartAttr, ArtistGetTopAlbumsResponse, ArtistTopAlbum, ArtistTopAlbums,
+};
+pub use artist_get_top_tracks::{ArtistGetTopTracksResponse, ArtistTopTrack, ArtistTopTracks};
+pub use auth_get_token::AuthGetTokenResponse;
+pub use auth_session::{AuthGetMobileSessionResponse, AuthGetSessionResponse, Session};
+pub use common::{ArtistSummary, Image, PageInfo, TagList, TagSummary, Wiki};
+pub use search::{
+    AlbumMatch, AlbumSearchResponse, ArtistMatch, ArtistSearchResponse, SearchMatch, SearchResults,
+    TrackMatch, TrackSearchResponse,
+};
 use serde_json::{to_string_pretty, Value};
+pub use track_get_correction::{
+    CorrectedTrack, CorrectionAttr, TrackCorrection, TrackCorrections, TrackGetCorrectionResponse,
+};
+pub use track_get_info::{TrackAlbum, TrackAlbumAttr, TrackGetInfoResponse, TrackInfo};
+pub use track_get_similar::{
+    SimilarTrack, SimilarTracks, SimilarTracksAttr, TrackGetSimilarResponse,
+};
+pub use track_scrobble::{
+    Corrected, IgnoredCode, IgnoredMessage, NowPlaying, Scrobble, TrackScrobbleResponse,
+    TrackUpdateNowPlayingResponse,
+};
+pub use user_get_recent_tracks::{
+    RecentTrack, RecentTrackAlbum, RecentTrackArtist, RecentTracks, UserGetRecentTracksResponse,
+};
 
 use crate::ApiError;
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,246 @@
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use super::common::{Image, PageInfo};
+use super::de::{one_or_many, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlbumSearchResponse {
+    pub results: SearchResults<AlbumMatch>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistSearchResponse {
+    pub results: SearchResults<ArtistMatch>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackSearchResponse {
+    pub results: SearchResults<TrackMatch>,
+}
+
+/// A result type of a search method, and where Last.fm lists it, e.g. `albummatches.album`.
+pub trait SearchMatch: DeserializeOwned {
+    const MATCHES: &'static str;
+    const ITEM: &'static str;
+}
+
+/// A page of search results.
+///
+/// Last.fm reports the counts as `opensearch:totalResults`, `opensearch:startIndex` and
+/// `opensearch:itemsPerPage` strings, and nests the matches under e.g. `albummatches.album`.
+/// Serializing writes the same shape back, so the results can be cached and read again.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SearchResults<T> {
+    /// The search terms.
+    pub query: String,
+    pub total_results: u64,
+    pub start_index: u64,
+    pub items_per_page: u64,
+    pub matches: Vec<T>,
+}
+
+impl<T> SearchResults<T> {
+    pub fn page_info(&self) -> PageInfo {
+        PageInfo::from_opensearch(self.total_results, self.start_index, self.items_per_page)
+    }
+
+    /// Whether there are results after this page.
+    pub fn has_more(&self) -> bool {
+        self.start_index + (self.matches.len() as u64) < self.total_results
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchPayload {
+    #[serde(
+        rename = "opensearch:totalResults",
+        default,
+        deserialize_with = "u64_from_str"
+    )]
+    total_results: u64,
+    #[serde(
+        rename = "opensearch:startIndex",
+        default,
+        deserialize_with = "u64_from_str"
+    )]
+    start_index: u64,
+    #[serde(
+        rename = "opensearch:itemsPerPage",
+        default,
+        deserialize_with = "u64_from_str"
+    )]
+    items_per_page: u64,
+    #[serde(rename = "@attr", default)]
+    attr: SearchAttr,
+    #[serde(flatten)]
+    rest: serde_json::Map<String, Value>,
+}
+
+#[derive(Default, Deserialize)]
+struct SearchAttr {
+    #[serde(rename = "for", default)]
+    query: String,
+}
+
+impl<'de, T: SearchMatch> Deserialize<'de> for SearchResults<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut payload = SearchPayload::deserialize(deserializer)?;
+        let items = match payload.rest.remove(T::MATCHES) {
+            Some(Value::Object(mut matches)) => matches.remove(T::ITEM).unwrap_or(Value::Null),
+            _ => Value::Null,
+        };
+        let matches = one_or_many(items).map_err(D::Error::custom)?;
+
+        Ok(SearchResults {
+            query: payload.attr.query,
+            total_results: payload.total_results,
+            start_index: payload.start_index,
+            items_per_page: payload.items_per_page,
+            matches,
+        })
+    }
+}
+
+impl<T: SearchMatch + Serialize> Serialize for SearchResults<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("opensearch:totalResults", &self.total_results)?;
+        map.serialize_entry("opensearch:startIndex", &self.start_index)?;
+        map.serialize_entry("opensearch:itemsPerPage", &self.items_per_page)?;
+        map.serialize_entry(T::MATCHES, &json!({ T::ITEM: &self.matches }))?;
+        map.serialize_entry("@attr", &json!({ "for": &self.query }))?;
+        map.end()
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlbumMatch {
+    pub name: String,
+    pub artist: String,
+    #[serde(default)]
+    pub mbid: String,
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+impl SearchMatch for AlbumMatch {
+    const MATCHES: &'static str = "albummatches";
+    const ITEM: &'static str = "album";
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistMatch {
+    pub name: String,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub listeners: u64,
+    #[serde(default)]
+    pub mbid: String,
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+impl SearchMatch for ArtistMatch {
+    const MATCHES: &'static str = "artistmatches";
+    const ITEM: &'static str = "artist";
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackMatch {
+    pub name: String,
+    pub artist: String,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub listeners: u64,
+    #[serde(default)]
+    pub mbid: String,
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+impl SearchMatch for TrackMatch {
+    const MATCHES: &'static str = "trackmatches";
+    const ITEM: &'static str = "track";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_album_search_response() {
+        let json = r##"{"results":{
+            "opensearch:Query":{"#text":"","role":"request","searchTerms":"believe","startPage":"2"},
+            "opensearch:totalResults":"126813","opensearch:startIndex":"30","opensearch:itemsPerPage":"30",
+            "albummatches":{"album":[
+                {"name":"Believe","artist":"Cher","url":"https://www.last.fm/music/Cher/Believe",
+                 "image":[{"#text":"","size":"small"}],"streamable":"0","mbid":"63b3a8ca-26f2-4e2b-b867-647a6ec2bebd"}
+            ]},
+            "@attr":{"for":"believe"}}}"##;
+        let response: AlbumSearchResponse = serde_json::from_str(json).unwrap();
+        let results = response.results;
+
+        assert_eq!(results.query, "believe");
+        assert_eq!(results.total_results, 126_813);
+        assert_eq!(results.start_index, 30);
+        assert_eq!(results.items_per_page, 30);
+        assert_eq!(results.matches[0].artist, "Cher");
+        assert_eq!(results.page_info().page, 2);
+        assert!(results.has_more());
+    }
+
+    #[test]
+    fn test_track_search_single_and_empty() {
+        let json = r#"{"results":{"opensearch:totalResults":"1","opensearch:startIndex":"0",
+            "opensearch:itemsPerPage":"30","trackmatches":{"track":{"name":"Believe","artist":"Cher",
+            "url":"https://www.last.fm/music/Cher/_/Believe","streamable":"FIXME","listeners":"1098475"}},
+            "@attr":{"for":"believe"}}}"#;
+        let response: TrackSearchResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.results.matches.len(), 1);
+        assert_eq!(response.results.matches[0].listeners, 1_098_475);
+        assert!(!response.results.has_more());
+
+        let json = r#"{"results":{"opensearch:totalResults":"0","opensearch:startIndex":"0",
+            "opensearch:itemsPerPage":"30","artistmatches":{"artist":[]},"@attr":{"for":"zzzz"}}}"#;
+        let response: ArtistSearchResponse = serde_json::from_str(json).unwrap();
+        assert!(response.results.matches.is_empty());
+    }
+
+    #[test]
+    fn test_search_results_round_trip() {
+        let response = ArtistSearchResponse {
+            results: SearchResults {
+                query: "cher".to_string(),
+                total_results: 31,
+                start_index: 30,
+                items_per_page: 30,
+                matches: vec![ArtistMatch {
+                    name: "Cher".to_string(),
+                    listeners: 1_000,
+                    url: "https://www.last.fm/music/Cher".to_string(),
+                    ..Default::default()
+                }],
+            },
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        let decoded: ArtistSearchResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, response);
+    }
+}
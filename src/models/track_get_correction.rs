@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::ArtistSummary;
+use super::de::{bool_from_str, one_or_many, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackGetCorrectionResponse {
+    #[serde(default)]
+    pub corrections: TrackCorrections,
+}
+
+impl TrackGetCorrectionResponse {
+    /// The suggested correction, if Last.fm has one.
+    pub fn correction(&self) -> Option<&TrackCorrection> {
+        self.corrections.correction.first()
+    }
+
+    /// Whether the artist or track name was corrected.
+    pub fn is_corrected(&self) -> bool {
+        self.correction().is_some_and(|correction| {
+            correction.attr.artistcorrected || correction.attr.trackcorrected
+        })
+    }
+}
+
+/// Last.fm sends a blank string instead of an object when there is nothing to correct.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CorrectionsPayload")]
+pub struct TrackCorrections {
+    pub correction: Vec<TrackCorrection>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackCorrection {
+    pub track: CorrectedTrack,
+    #[serde(rename = "@attr", default)]
+    pub attr: CorrectionAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectedTrack {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default)]
+    pub url: String,
+    pub artist: ArtistSummary,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionAttr {
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub index: u64,
+    #[serde(default, deserialize_with = "bool_from_str")]
+    pub artistcorrected: bool,
+    #[serde(default, deserialize_with = "bool_from_str")]
+    pub trackcorrected: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CorrectionsPayload {
+    Corrections {
+        #[serde(default, deserialize_with = "one_or_many")]
+        correction: Vec<TrackCorrection>,
+    },
+    Blank(String),
+}
+
+impl TryFrom<CorrectionsPayload> for TrackCorrections {
+    type Error = String;
+
+    fn try_from(payload: CorrectionsPayload) -> Result<Self, Self::Error> {
+        match payload {
+            CorrectionsPayload::Corrections { correction } => Ok(TrackCorrections { correction }),
+            CorrectionsPayload::Blank(text) if text.trim().is_empty() => {
+                Ok(TrackCorrections::default())
+            }
+            CorrectionsPayload::Blank(text) => Err(format!("unexpected corrections: {}", text)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_correction_response() {
+        let json = r#"{"corrections":{"correction":{"track":{"name":"Believe","mbid":"",
+            "url":"https://www.last.fm/music/Cher/_/Believe",
+            "artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818","url":"https://www.last.fm/music/Cher"}},
+            "@attr":{"index":"0","artistcorrected":"0","trackcorrected":"1"}}}}"#;
+        let response: TrackGetCorrectionResponse = serde_json::from_str(json).unwrap();
+
+        assert!(response.is_corrected());
+        let correction = response.correction().unwrap();
+        assert_eq!(correction.track.name, "Believe");
+        assert_eq!(correction.track.artist.name, "Cher");
+        assert!(!correction.attr.artistcorrected);
+        assert!(correction.attr.trackcorrected);
+    }
+
+    #[test]
+    fn test_track_without_correction() {
+        let json = r#"{"corrections":"\n    "}"#;
+        let response: TrackGetCorrectionResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.correction(), None);
+        assert!(!response.is_corrected());
+    }
+}
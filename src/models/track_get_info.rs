@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::{ArtistSummary, Image, TagList, Wiki};
+use super::de::{
+    duration_from_millis, one_or_many, option_bool_from_str, option_u64_from_str, u64_from_str,
+};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackGetInfoResponse {
+    pub track: TrackInfo,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackInfo {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    pub url: String,
+    /// `None` when Last.fm does not know the length of the track.
+    #[serde(default, deserialize_with = "duration_from_millis")]
+    pub duration: Option<Duration>,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub listeners: u64,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub playcount: u64,
+    pub artist: ArtistSummary,
+    #[serde(default)]
+    pub album: Option<TrackAlbum>,
+    /// Only reported when the request names a `username`.
+    #[serde(default, deserialize_with = "option_u64_from_str")]
+    pub userplaycount: Option<u64>,
+    /// Only reported when the request names a `username`.
+    #[serde(default, deserialize_with = "option_bool_from_str")]
+    pub userloved: Option<bool>,
+    #[serde(default)]
+    pub toptags: TagList,
+    #[serde(default)]
+    pub wiki: Option<Wiki>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackAlbum {
+    pub artist: String,
+    pub title: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+    #[serde(rename = "@attr", default)]
+    pub attr: TrackAlbumAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackAlbumAttr {
+    /// The position of the track on the album.
+    #[serde(default, deserialize_with = "option_u64_from_str")]
+    pub position: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_info_response() {
+        let json = r##"{"track":{"name":"Believe","mbid":"32ca187e-ee25-4f18-b7d0-3b6713f24635",
+            "url":"https://www.last.fm/music/Cher/_/Believe","duration":"240000",
+            "streamable":{"#text":"0","fulltrack":"0"},"listeners":"1098475","playcount":"8516093",
+            "artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818","url":"https://www.last.fm/music/Cher"},
+            "album":{"artist":"Cher","title":"Believe","mbid":"63b3a8ca-26f2-4e2b-b867-647a6ec2bebd",
+                "url":"https://www.last.fm/music/Cher/Believe",
+                "image":[{"#text":"https://img/s.png","size":"small"}],"@attr":{"position":"1"}},
+            "userplaycount":"27","userloved":"1",
+            "toptags":{"tag":[{"name":"pop","url":"https://www.last.fm/tag/pop"}]},
+            "wiki":{"published":"27 Jul 2008, 15:44","summary":"Believe is a song.","content":"Believe is a song by Cher."}}}"##;
+        let response: TrackGetInfoResponse = serde_json::from_str(json).unwrap();
+        let track = response.track;
+
+        assert_eq!(track.duration, Some(Duration::from_secs(240)));
+        assert_eq!(track.listeners, 1_098_475);
+        assert_eq!(track.userplaycount, Some(27));
+        assert_eq!(track.userloved, Some(true));
+        let album = track.album.unwrap();
+        assert_eq!(album.title, "Believe");
+        assert_eq!(album.image[0].url, "https://img/s.png");
+        assert_eq!(album.attr.position, Some(1));
+        assert_eq!(track.toptags.tag[0].name, "pop");
+        assert_eq!(track.wiki.unwrap().summary, "Believe is a song.");
+    }
+
+    #[test]
+    fn test_track_info_without_user_or_album() {
+        let json = r#"{"track":{"name":"Believe","url":"https://www.last.fm/music/Cher/_/Believe",
+            "duration":"0","listeners":"1","playcount":"1",
+            "artist":{"name":"Cher","url":"https://www.last.fm/music/Cher"},"toptags":{"tag":[]}}}"#;
+        let response: TrackGetInfoResponse = serde_json::from_str(json).unwrap();
+        let track = response.track;
+
+        assert_eq!(track.duration, None);
+        assert_eq!(track.album, None);
+        assert_eq!(track.userplaycount, None);
+        assert_eq!(track.userloved, None);
+        assert_eq!(track.wiki, None);
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::{ArtistSummary, Image};
+use super::de::{duration_from_secs, f64_from_str, one_or_many, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackGetSimilarResponse {
+    pub similartracks: SimilarTracks,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarTracks {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub track: Vec<SimilarTrack>,
+    #[serde(rename = "@attr", default)]
+    pub attr: SimilarTracksAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarTracksAttr {
+    /// The artist of the track the others are similar to.
+    #[serde(default)]
+    pub artist: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarTrack {
+    pub name: String,
+    #[serde(default)]
+    pub mbid: String,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub playcount: u64,
+    /// How similar the track is, from 0 to 1.
+    #[serde(rename = "match", default, deserialize_with = "f64_from_str")]
+    pub match_score: f64,
+    pub url: String,
+    /// Reported in seconds by this method. `None` when unknown.
+    #[serde(default, deserialize_with = "duration_from_secs")]
+    pub duration: Option<Duration>,
+    pub artist: ArtistSummary,
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub image: Vec<Image>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_tracks_response() {
+        let json = r##"{"similartracks":{"track":[
+            {"name":"Strong Enough","playcount":459386,"mbid":"","match":1.0,
+             "url":"https://www.last.fm/music/Cher/_/Strong+Enough",
+             "streamable":{"#text":"0","fulltrack":"0"},"duration":223,
+             "artist":{"name":"Cher","mbid":"bfcc6d75-a6a5-4bc6-8282-47aec8531818","url":"https://www.last.fm/music/Cher"},
+             "image":[{"#text":"","size":"small"}]},
+            {"name":"Vogue","playcount":"1200000","match":"0.507",
+             "url":"https://www.last.fm/music/Madonna/_/Vogue","duration":0,
+             "artist":{"name":"Madonna","url":"https://www.last.fm/music/Madonna"},"image":[]}
+        ],"@attr":{"artist":"Cher"}}}"##;
+        let response: TrackGetSimilarResponse = serde_json::from_str(json).unwrap();
+        let similar = response.similartracks;
+
+        assert_eq!(similar.attr.artist, "Cher");
+        assert_eq!(similar.track[0].match_score, 1.0);
+        assert_eq!(similar.track[0].duration, Some(Duration::from_secs(223)));
+        assert_eq!(similar.track[1].match_score, 0.507);
+        assert_eq!(similar.track[1].playcount, 1_200_000);
+        assert_eq!(similar.track[1].duration, None);
+    }
+}
@@ -0,0 +1,231 @@
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::de::{bool_from_str, one_or_many, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackScrobbleResponse {
+    pub scrobbles: Scrobbles,
+}
+
+impl TrackScrobbleResponse {
+    /// The number of scrobbles Last.fm accepted.
+    pub fn accepted(&self) -> u64 {
+        self.scrobbles.attr.accepted
+    }
+
+    /// The number of scrobbles Last.fm ignored.
+    pub fn ignored(&self) -> u64 {
+        self.scrobbles.attr.ignored
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scrobbles {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub scrobble: Vec<Scrobble>,
+    #[serde(rename = "@attr")]
+    pub attr: ScrobblesAttr,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrobblesAttr {
+    #[serde(deserialize_with = "u64_from_str")]
+    pub accepted: u64,
+    #[serde(deserialize_with = "u64_from_str")]
+    pub ignored: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scrobble {
+    #[serde(default)]
+    pub artist: Corrected,
+    #[serde(default)]
+    pub track: Corrected,
+    #[serde(default)]
+    pub album: Corrected,
+    #[serde(default)]
+    pub album_artist: Corrected,
+    #[serde(default, deserialize_with = "u64_from_str")]
+    pub timestamp: u64,
+    pub ignored_message: IgnoredMessage,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackUpdateNowPlayingResponse {
+    pub nowplaying: NowPlaying,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NowPlaying {
+    #[serde(default)]
+    pub artist: Corrected,
+    #[serde(default)]
+    pub track: Corrected,
+    #[serde(default)]
+    pub album: Corrected,
+    #[serde(default)]
+    pub album_artist: Corrected,
+    pub ignored_message: IgnoredMessage,
+}
+
+/// A value echoed back by Last.fm, flagged when it was changed by auto-correction.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Corrected {
+    #[serde(deserialize_with = "bool_from_str")]
+    pub corrected: bool,
+    #[serde(rename = "#text", default)]
+    pub text: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoredMessage {
+    pub code: IgnoredCode,
+    #[serde(rename = "#text", default)]
+    pub text: String,
+}
+
+/// The reason Last.fm gave for ignoring a scrobble or now-playing update.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IgnoredCode {
+    #[default]
+    NotIgnored,
+    ArtistIgnored,
+    TrackIgnored,
+    TimestampTooOld,
+    TimestampTooNew,
+    DailyScrobbleLimitExceeded,
+    Unknown(u64),
+}
+
+impl IgnoredCode {
+    pub fn code(&self) -> u64 {
+        match self {
+            IgnoredCode::NotIgnored => 0,
+            IgnoredCode::ArtistIgnored => 1,
+            IgnoredCode::TrackIgnored => 2,
+            IgnoredCode::TimestampTooOld => 3,
+            IgnoredCode::TimestampTooNew => 4,
+            IgnoredCode::DailyScrobbleLimitExceeded => 5,
+            IgnoredCode::Unknown(code) => *code,
+        }
+    }
+
+    pub fn is_ignored(&self) -> bool {
+        *self != IgnoredCode::NotIgnored
+    }
+}
+
+impl From<u64> for IgnoredCode {
+    fn from(code: u64) -> Self {
+        match code {
+            0 => IgnoredCode::NotIgnored,
+            1 => IgnoredCode::ArtistIgnored,
+            2 => IgnoredCode::TrackIgnored,
+            3 => IgnoredCode::TimestampTooOld,
+            4 => IgnoredCode::TimestampTooNew,
+            5 => IgnoredCode::DailyScrobbleLimitExceeded,
+            code => IgnoredCode::Unknown(code),
+        }
+    }
+}
+
+impl fmt::Display for IgnoredCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IgnoredCode::NotIgnored => write!(f, "Not ignored"),
+            IgnoredCode::ArtistIgnored => write!(f, "Artist was ignored"),
+            IgnoredCode::TrackIgnored => write!(f, "Track was ignored"),
+            IgnoredCode::TimestampTooOld => write!(f, "Timestamp was too old"),
+            IgnoredCode::TimestampTooNew => write!(f, "Timestamp was too new"),
+            IgnoredCode::DailyScrobbleLimitExceeded => write!(f, "Daily scrobble limit exceeded"),
+            IgnoredCode::Unknown(code) => write!(f, "Ignored for an unknown reason ({})", code),
+        }
+    }
+}
+
+impl Serialize for IgnoredCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for IgnoredCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64_from_str(deserializer).map(IgnoredCode::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrobble_response() {
+        let json = r##"{"scrobbles":{"scrobble":[
+            {"artist":{"corrected":"1","#text":"Billie Eilish"},"album":{"corrected":"0"},
+             "track":{"corrected":"0","#text":"CHIHIRO"},"albumArtist":{"corrected":"0","#text":""},
+             "timestamp":"1700000000","ignoredMessage":{"code":"0","#text":""}},
+            {"artist":{"corrected":"0","#text":"A"},"album":{"corrected":"0"},
+             "track":{"corrected":"0","#text":"T"},"albumArtist":{"corrected":"0","#text":""},
+             "timestamp":"1600000000","ignoredMessage":{"code":"3","#text":"Timestamp too old"}}],
+            "@attr":{"accepted":1,"ignored":1}}}"##;
+        let response: TrackScrobbleResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.accepted(), 1);
+        assert_eq!(response.ignored(), 1);
+
+        let scrobbles = &response.scrobbles.scrobble;
+        assert!(scrobbles[0].artist.corrected);
+        assert_eq!(scrobbles[0].artist.text, "Billie Eilish");
+        assert_eq!(scrobbles[0].timestamp, 1700000000);
+        assert!(!scrobbles[0].ignored_message.code.is_ignored());
+        assert_eq!(
+            scrobbles[1].ignored_message.code,
+            IgnoredCode::TimestampTooOld
+        );
+    }
+
+    #[test]
+    fn test_single_scrobble_rendered_as_object() {
+        let json = r##"{"scrobbles":{"scrobble":{"timestamp":"1700000000",
+            "ignoredMessage":{"code":"1","#text":"Artist was ignored"}},
+            "@attr":{"accepted":0,"ignored":1}}}"##;
+        let response: TrackScrobbleResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.scrobbles.scrobble.len(), 1);
+        assert_eq!(
+            response.scrobbles.scrobble[0].ignored_message.code,
+            IgnoredCode::ArtistIgnored
+        );
+    }
+
+    #[test]
+    fn test_now_playing_response() {
+        let json = r##"{"nowplaying":{"artist":{"corrected":"0","#text":"A"},
+            "track":{"corrected":"0","#text":"T"},"album":{"corrected":"0"},
+            "albumArtist":{"corrected":"0","#text":""},
+            "ignoredMessage":{"code":"1","#text":"Artist was ignored"}}}"##;
+        let response: TrackUpdateNowPlayingResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.nowplaying.track.text, "T");
+        assert_eq!(
+            response.nowplaying.ignored_message.code,
+            IgnoredCode::ArtistIgnored
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::{Image, PageInfo};
+use super::de::{bool_from_str, one_or_many, option_bool_from_str, u64_from_str};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGetRecentTracksResponse {
+    pub recenttracks: RecentTracks,
+}
+
+/// A page of a user's listening history.
+///
+/// The track currently playing, if any, is listed first on every page with `now_playing` set
+/// and no `played_at` timestamp. It serializes back into the payload Last.fm sends.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawRecentTracks", into = "RawRecentTracks")]
+pub struct RecentTracks {
+    pub user: String,
+    pub page_info: PageInfo,
+    pub tracks: Vec<RecentTrack>,
+}
+
+impl RecentTracks {
+    /// The track the user is listening to right now.
+    pub fn now_playing(&self) -> Option<&RecentTrack> {
+        self.tracks.iter().find(|track| track.now_playing)
+    }
+
+    /// The tracks that were scrobbled, without the now-playing entry.
+    pub fn scrobbles(&self) -> impl Iterator<Item = &RecentTrack> {
+        self.tracks.iter().filter(|track| !track.now_playing)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentTrack {
+    pub artist: RecentTrackArtist,
+    pub album: Option<RecentTrackAlbum>,
+    pub name: String,
+    pub mbid: Option<String>,
+    pub url: String,
+    /// When the track was scrobbled, as a Unix timestamp. `None` for the now-playing entry.
+    pub played_at: Option<u64>,
+    pub now_playing: bool,
+    /// Whether the user loved the track. Only reported when `extended` is set.
+    pub loved: Option<bool>,
+    pub images: Vec<Image>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentTrackArtist {
+    pub name: String,
+    pub mbid: Option<String>,
+    /// Only reported when `extended` is set.
+    pub url: Option<String>,
+    /// Only reported when `extended` is set.
+    pub images: Vec<Image>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentTrackAlbum {
+    pub name: String,
+    pub mbid: Option<String>,
+}
+
+/// The payload as Last.fm sends it, before normalization.
+#[derive(Serialize, Deserialize)]
+struct RawRecentTracks {
+    #[serde(default, deserialize_with = "one_or_many")]
+    track: Vec<RawRecentTrack>,
+    #[serde(rename = "@attr", default)]
+    attr: RawRecentTracksAttr,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawRecentTracksAttr {
+    #[serde(default)]
+    user: String,
+    #[serde(flatten)]
+    page_info: PageInfo,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawRecentTrack {
+    artist: RawArtist,
+    #[serde(default)]
+    album: RawText,
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<RawDate>,
+    #[serde(rename = "@attr", default)]
+    attr: RawTrackAttr,
+    #[serde(
+        default,
+        deserialize_with = "option_bool_from_str",
+        skip_serializing_if = "Option::is_none"
+    )]
+    loved: Option<bool>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    image: Vec<Image>,
+}
+
+/// The artist is `{"#text", "mbid"}`, or `{"name", "mbid", "url", "image"}` with `extended=1`.
+#[derive(Default, Serialize, Deserialize)]
+struct RawArtist {
+    #[serde(rename = "#text", default, skip_serializing_if = "String::is_empty")]
+    text: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    mbid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    image: Vec<Image>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawText {
+    #[serde(rename = "#text", default)]
+    text: String,
+    #[serde(default)]
+    mbid: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawDate {
+    #[serde(deserialize_with = "u64_from_str")]
+    uts: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RawTrackAttr {
+    #[serde(default, deserialize_with = "bool_from_str")]
+    nowplaying: bool,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    Some(value).filter(|value| !value.is_empty())
+}
+
+impl From<RawRecentTracks> for RecentTracks {
+    fn from(raw: RawRecentTracks) -> Self {
+        RecentTracks {
+            user: raw.attr.user,
+            page_info: raw.attr.page_info,
+            tracks: raw.track.into_iter().map(RecentTrack::from).collect(),
+        }
+    }
+}
+
+impl From<RawRecentTrack> for RecentTrack {
+    fn from(raw: RawRecentTrack) -> Self {
+        let artist = raw.artist;
+        let album = non_empty(raw.album.text).map(|name| RecentTrackAlbum {
+            name,
+            mbid: non_empty(raw.album.mbid),
+        });
+
+        RecentTrack {
+            artist: RecentTrackArtist {
+                name: if artist.name.is_empty() {
+                    artist.text
+                } else {
+                    artist.name
+                },
+                mbid: non_empty(artist.mbid),
+                url: artist.url.and_then(non_empty),
+                images: artist.image,
+            },
+            album,
+            name: raw.name,
+            mbid: non_empty(raw.mbid),
+            url: raw.url,
+            played_at: raw
+                .date
+                .map(|date| date.uts)
+                .filter(|_| !raw.attr.nowplaying),
+            now_playing: raw.attr.nowplaying,
+            loved: raw.loved,
+            images: raw.image,
+        }
+    }
+}
+
+impl From<RecentTracks> for RawRecentTracks {
+    fn from(recent: RecentTracks) -> Self {
+        RawRecentTracks {
+            track: recent
+                .tracks
+                .into_iter()
+                .map(RawRecentTrack::from)
+                .collect(),
+            attr: RawRecentTracksAttr {
+                user: recent.user,
+                page_info: recent.page_info,
+            },
+        }
+    }
+}
+
+impl From<RecentTrack> for RawRecentTrack {
+    fn from(track: RecentTrack) -> Self {
+        let album = track.album.unwrap_or_default();
+
+        RawRecentTrack {
+            artist: RawArtist {
+                text: String::new(),
+                name: track.artist.name,
+                mbid: track.artist.mbid.unwrap_or_default(),
+                url: track.artist.url,
+                image: track.artist.images,
+            },
+            album: RawText {
+                text: album.name,
+                mbid: album.mbid.unwrap_or_default(),
+            },
+            name: track.name,
+            mbid: track.mbid.unwrap_or_default(),
+            url: track.url,
+            date: track.played_at.map(|uts| RawDate { uts }),
+            attr: RawTrackAttr {
+                nowplaying: track.now_playing,
+            },
+            loved: track.loved,
+            image: track.images,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_tracks_with_now_playing() {
+        let json = r##"{"recenttracks":{"track":[
+            {"artist":{"mbid":"","#text":"ABBA"},"streamable":"0","image":[{"size":"small","#text":"https://img/s.png"}],
+             "mbid":"","album":{"mbid":"","#text":""},"name":"Dancing Queen","url":"https://www.last.fm/music/ABBA/_/Dancing+Queen",
+             "@attr":{"nowplaying":"true"}},
+            {"artist":{"mbid":"bfcc6d75","#text":"Cher"},"streamable":"0","image":[],
+             "mbid":"","album":{"mbid":"","#text":"Believe"},"name":"Believe","url":"https://www.last.fm/music/Cher/_/Believe",
+             "date":{"uts":"1700000000","#text":"14 Nov 2023, 22:13"}}
+        ],"@attr":{"user":"uppercase_","totalPages":"3","page":"1","perPage":"1","total":"3"}}}"##;
+        let response: UserGetRecentTracksResponse = serde_json::from_str(json).unwrap();
+        let recent = response.recenttracks;
+
+        assert_eq!(recent.user, "uppercase_");
+        assert_eq!(recent.page_info.total_pages, 3);
+        assert!(recent.page_info.has_next_page());
+
+        let now_playing = recent.now_playing().unwrap();
+        assert_eq!(now_playing.name, "Dancing Queen");
+        assert_eq!(now_playing.played_at, None);
+        assert_eq!(now_playing.album, None);
+        assert_eq!(now_playing.images[0].url, "https://img/s.png");
+
+        let scrobbles: Vec<_> = recent.scrobbles().collect();
+        assert_eq!(scrobbles.len(), 1);
+        assert_eq!(scrobbles[0].artist.name, "Cher");
+        assert_eq!(scrobbles[0].artist.mbid.as_deref(), Some("bfcc6d75"));
+        assert_eq!(scrobbles[0].album.as_ref().unwrap().name, "Believe");
+        assert_eq!(scrobbles[0].played_at, Some(1_700_000_000));
+        assert_eq!(scrobbles[0].loved, None);
+    }
+
+    #[test]
+    fn test_extended_recent_track_as_single_object() {
+        let json = r##"{"recenttracks":{"track":
+            {"artist":{"url":"https://www.last.fm/music/Cher","name":"Cher","image":[{"size":"small","#text":""}],"mbid":""},
+             "date":{"uts":"1700000000","#text":"14 Nov 2023, 22:13"},"mbid":"","name":"Believe",
+             "image":[],"url":"","streamable":"0","album":{"mbid":"","#text":"Believe"},"loved":"1"},
+            "@attr":{"user":"uppercase_","totalPages":"1","page":"1","perPage":"50","total":"1"}}}"##;
+        let response: UserGetRecentTracksResponse = serde_json::from_str(json).unwrap();
+        let tracks = response.recenttracks.tracks;
+
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].artist.name, "Cher");
+        assert_eq!(
+            tracks[0].artist.url.as_deref(),
+            Some("https://www.last.fm/music/Cher")
+        );
+        assert_eq!(tracks[0].artist.images.len(), 1);
+        assert_eq!(tracks[0].loved, Some(true));
+        assert!(!tracks[0].now_playing);
+    }
+
+    #[test]
+    fn test_recent_tracks_round_trip() {
+        let json = r##"{"recenttracks":{"track":[
+            {"artist":{"mbid":"","#text":"ABBA"},"image":[],"mbid":"","album":{"mbid":"","#text":""},
+             "name":"Dancing Queen","url":"","@attr":{"nowplaying":"true"}},
+            {"artist":{"mbid":"bfcc6d75","#text":"Cher"},"image":[],"mbid":"","album":{"mbid":"","#text":"Believe"},
+             "name":"Believe","url":"","date":{"uts":"1700000000","#text":"14 Nov 2023, 22:13"},"loved":"0"}
+        ],"@attr":{"user":"uppercase_","totalPages":"3","page":"1","perPage":"1","total":"3"}}}"##;
+        let response: UserGetRecentTracksResponse = serde_json::from_str(json).unwrap();
+
+        let json = serde_json::to_string(&response).unwrap();
+        let decoded: UserGetRecentTracksResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, response);
+    }
+}
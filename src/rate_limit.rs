@@ -0,0 +1,102 @@
+//! Client-side throttling to stay within Last.fm's request rate guidelines.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+
+/// A token bucket shared by every clone of the [`Lastfm`](crate::Lastfm) client it is set on.
+///
+/// Cloning a `RateLimiter` yields a handle to the same bucket, so concurrent tasks are
+/// throttled globally. The default allows 5 requests per second, as recommended by Last.fm.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    tokens_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::per_second(5)
+    }
+}
+
+impl RateLimiter {
+    /// Allows `requests` requests every `period`, with bursts of up to `requests`.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+        let period = period.as_secs_f64().max(f64::EPSILON);
+
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                tokens_per_sec: capacity / period,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        RateLimiter::new(requests, Duration::from_secs(1))
+    }
+
+    /// Waits until a request may be sent.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|err| err.into_inner());
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * bucket.tokens_per_sec).min(bucket.capacity);
+            bucket.last_refill = now;
+
+            // Reserve a token even if none is available yet, so that waiting callers are
+            // served in the order they arrived without holding the lock while sleeping.
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / bucket.tokens_per_sec)
+        };
+
+        sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_burst_then_throttle() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(100));
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_the_bucket() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(100));
+        let clone = limiter.clone();
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        clone.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
@@ -0,0 +1,166 @@
+//! Retrying of requests that failed because of transient Last.fm or network errors.
+
+use std::time::Duration;
+
+use crate::{APIResponse, ApiErrorCode, Error, Result};
+
+/// Controls how [`Lastfm::send_request`](crate::Lastfm::send_request) retries failed requests.
+///
+/// Read-only requests are retried whenever the failure is transient. Write methods, such as
+/// `track.scrobble`, are only retried if [`RetryPolicy::retry_writes`] is enabled, since a
+/// request that timed out may still have been applied by Last.fm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+    /// The Last.fm errors worth retrying. `None` retries the ones
+    /// [`ApiErrorCode::is_retryable`] reports as transient.
+    pub retryable_codes: Option<Vec<ApiErrorCode>>,
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with exponential backoff starting at 500ms, retrying the errors
+    /// [`ApiErrorCode::is_retryable`] considers transient.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable_codes: None,
+            retry_writes: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry. It doubles with every following attempt.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// The upper bound for the delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Randomizes each delay between half and all of its value, so that clients started
+    /// together do not retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The Last.fm errors that are considered transient, instead of the default ones.
+    pub fn retryable_codes(mut self, retryable_codes: Vec<ApiErrorCode>) -> Self {
+        self.retryable_codes = Some(retryable_codes);
+        self
+    }
+
+    /// Whether write methods, such as scrobbles, may be retried as well.
+    pub fn retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    /// The delay to wait after the given failed attempt, starting at 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let millis = delay.as_millis() as u64;
+            Duration::from_millis(fastrand::u64(millis / 2..=millis))
+        } else {
+            delay
+        }
+    }
+
+    /// Whether the outcome of a request is a transient failure worth retrying.
+    pub fn is_retryable<T>(&self, outcome: &Result<APIResponse<T>>) -> bool {
+        match outcome {
+            Ok(APIResponse::Error(err)) => match &self.retryable_codes {
+                Some(codes) => codes.contains(&err.code()),
+                None => err.code().is_retryable(),
+            },
+            Err(Error::HttpStatus(status)) => *status >= 500,
+            Err(Error::NetworkError(err)) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiError;
+    use serde_json::Value;
+
+    #[test]
+    fn test_delay_backs_off_exponentially() {
+        let policy = RetryPolicy::default()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(350));
+        assert_eq!(policy.delay(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::default().base_delay(Duration::from_millis(100));
+
+        for _ in 0..100 {
+            let delay = policy.delay(1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let policy = RetryPolicy::default();
+        let api_error = |error| -> Result<APIResponse<Value>> {
+            Ok(APIResponse::Error(ApiError {
+                error,
+                ..Default::default()
+            }))
+        };
+
+        assert!(policy.is_retryable(&api_error(29)));
+        assert!(policy.is_retryable(&api_error(16)));
+        assert!(!policy.is_retryable(&api_error(6)));
+        assert!(policy.is_retryable::<Value>(&Err(Error::HttpStatus(503))));
+        assert!(!policy.is_retryable::<Value>(&Err(Error::HttpStatus(404))));
+        assert!(!policy.is_retryable(&Ok(APIResponse::Success(Value::Null))));
+
+        let policy = policy.retryable_codes(vec![ApiErrorCode::InvalidParameters]);
+        assert!(policy.is_retryable(&api_error(6)));
+        assert!(!policy.is_retryable(&api_error(29)));
+    }
+}
@@ -0,0 +1,371 @@
+//! A persistent queue for scrobbles that could not be submitted yet.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::session_store::tmp_path;
+use crate::{Error, Lastfm, Result, ScrobbleEntry, ScrobbleResult, MAX_SCROBBLES_PER_REQUEST};
+
+/// Last.fm rejects scrobbles with a timestamp older than 14 days.
+pub const SCROBBLE_MAX_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// A summary of what happened during [`ScrobbleQueue::flush`].
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    /// Entries that were submitted to Last.fm, including the ones it ignored.
+    pub submitted: Vec<ScrobbleResult>,
+    /// Entries that were dropped because they fell outside the acceptance window.
+    pub expired: Vec<ScrobbleEntry>,
+    /// The number of entries still waiting in the queue.
+    pub remaining: usize,
+    /// Why the flush stopped before the queue was empty, if it did.
+    pub stopped: Option<FlushStop>,
+}
+
+/// The reason [`ScrobbleQueue::flush`] stopped early. The unsent entries stay queued either way.
+#[derive(Debug)]
+pub enum FlushStop {
+    /// Last.fm could not be reached or is temporarily unavailable; a later flush may succeed.
+    Unavailable(Error),
+    /// Last.fm rejected a batch, e.g. because the session key is no longer valid. Flushing
+    /// again will fail the same way until the cause is fixed.
+    Rejected(Error),
+}
+
+impl FlushStop {
+    fn from_error(err: Error) -> Self {
+        let retryable = match &err {
+            Error::NetworkError(err) => err.is_connect() || err.is_timeout(),
+            Error::HttpStatus(status) => *status >= 500,
+            // An outage page served instead of JSON.
+            Error::JsonError(_) => true,
+            Error::ApiError(err) => err.code().is_retryable(),
+            _ => false,
+        };
+
+        if retryable {
+            FlushStop::Unavailable(err)
+        } else {
+            FlushStop::Rejected(err)
+        }
+    }
+
+    /// The error that stopped the flush.
+    pub fn error(&self) -> &Error {
+        match self {
+            FlushStop::Unavailable(err) | FlushStop::Rejected(err) => err,
+        }
+    }
+
+    /// Whether flushing again later may succeed without any other change.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, FlushStop::Unavailable(_))
+    }
+}
+
+/// A queue of pending scrobbles persisted to disk as JSON lines.
+///
+/// Every change is written back to the file, so entries survive restarts until they are
+/// successfully submitted with [`ScrobbleQueue::flush`].
+#[derive(Debug)]
+pub struct ScrobbleQueue {
+    path: PathBuf,
+    entries: Vec<ScrobbleEntry>,
+    keys: HashSet<(String, String, u64)>,
+}
+
+impl ScrobbleQueue {
+    /// Opens the queue stored at `path`, creating an empty one if the file does not exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut queue = ScrobbleQueue {
+            path: path.as_ref().to_path_buf(),
+            entries: Vec::new(),
+            keys: HashSet::new(),
+        };
+
+        if queue.path.exists() {
+            let reader = BufReader::new(File::open(&queue.path)?);
+            for line in reader.lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry: ScrobbleEntry = serde_json::from_str(&line)?;
+                queue.insert(entry);
+            }
+        }
+
+        Ok(queue)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[ScrobbleEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds an entry to the queue and persists it.
+    ///
+    /// Returns `false` if an entry with the same artist, track and timestamp is already queued.
+    /// An entry without an artist or track is rejected with `Error::Validation`, since Last.fm
+    /// would refuse it and block the entries queued behind it.
+    pub fn push(&mut self, entry: ScrobbleEntry) -> Result<bool> {
+        entry.validate()?;
+        if !self.insert(entry) {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Removes and returns the entries older than [`SCROBBLE_MAX_AGE`].
+    pub fn prune_expired(&mut self) -> Result<Vec<ScrobbleEntry>> {
+        let cutoff = unix_now().saturating_sub(SCROBBLE_MAX_AGE.as_secs());
+
+        let (expired, kept): (Vec<_>, Vec<_>) = self
+            .entries
+            .drain(..)
+            .partition(|entry| entry.timestamp < cutoff);
+        self.entries = kept;
+
+        if !expired.is_empty() {
+            for entry in &expired {
+                self.keys.remove(&key(entry));
+            }
+            self.save()?;
+        }
+
+        Ok(expired)
+    }
+
+    /// Submits the queued entries in batches of [`MAX_SCROBBLES_PER_REQUEST`].
+    ///
+    /// Expired entries are dropped first. Each batch is removed from the queue once Last.fm
+    /// has answered it, whether the individual scrobbles were accepted or ignored.
+    /// If a batch fails, the flush stops, the unsent entries stay queued and the report
+    /// records why in [`FlushReport::stopped`]. An `Err` is only returned when the queue file
+    /// cannot be written.
+    pub async fn flush(&mut self, lastfm: &Lastfm) -> Result<FlushReport> {
+        let mut report = FlushReport {
+            expired: self.prune_expired()?,
+            ..Default::default()
+        };
+
+        while !self.entries.is_empty() {
+            let count = self.entries.len().min(MAX_SCROBBLES_PER_REQUEST);
+            let chunk = self.entries[..count].to_vec();
+
+            let batch = match lastfm.track().scrobble_batch().entries(chunk).send().await {
+                Ok(batch) => batch,
+                Err(err) => {
+                    report.stopped = Some(FlushStop::from_error(err));
+                    break;
+                }
+            };
+
+            for entry in self.entries.drain(..batch.submitted.len()) {
+                self.keys.remove(&key(&entry));
+            }
+            self.save()?;
+            report.submitted.extend(batch.submitted);
+
+            if let Some(err) = batch.error {
+                report.stopped = Some(FlushStop::from_error(err));
+                break;
+            }
+        }
+
+        report.remaining = self.entries.len();
+        Ok(report)
+    }
+
+    fn insert(&mut self, entry: ScrobbleEntry) -> bool {
+        if !self.keys.insert(key(&entry)) {
+            return false;
+        }
+        self.entries.push(entry);
+        true
+    }
+
+    /// Rewrites the queue file, going through a temporary file so a crash never truncates it.
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = tmp_path(&self.path);
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn key(entry: &ScrobbleEntry) -> (String, String, u64) {
+    (entry.artist.clone(), entry.track.clone(), entry.timestamp)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::testing::{lastfm_with, scrobble_echo};
+    use crate::ValidationError;
+    use crate::{LastfmMethod, MockTransport};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn queue_with(path: &Path, count: u64) -> ScrobbleQueue {
+        let now = unix_now();
+        let mut queue = ScrobbleQueue::open(path).unwrap();
+        for i in 0..count {
+            queue
+                .push(ScrobbleEntry::new(
+                    "Cher",
+                    format!("Track {}", i),
+                    now - count + i,
+                ))
+                .unwrap();
+        }
+        queue
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_progress_when_service_is_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.jsonl");
+        let mut queue = queue_with(&path, MAX_SCROBBLES_PER_REQUEST as u64 + 1);
+
+        let transport = Arc::new(
+            MockTransport::new()
+                .respond_once(
+                    LastfmMethod::TrackScrobble,
+                    scrobble_echo(&queue.entries()[..MAX_SCROBBLES_PER_REQUEST]),
+                )
+                .respond_once(
+                    LastfmMethod::TrackScrobble,
+                    json!({ "error": 11, "message": "Service Offline" }),
+                ),
+        );
+        let report = queue.flush(&lastfm_with(transport)).await.unwrap();
+
+        assert_eq!(report.submitted.len(), MAX_SCROBBLES_PER_REQUEST);
+        assert_eq!(report.remaining, 1);
+        assert!(report.stopped.as_ref().unwrap().is_retryable());
+
+        let reopened = ScrobbleQueue::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(
+            reopened.entries()[0].track,
+            format!("Track {}", MAX_SCROBBLES_PER_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_classifies_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.jsonl");
+        let mut queue = queue_with(&path, 2);
+
+        let outage = Arc::new(MockTransport::new().respond_with_status(
+            LastfmMethod::TrackScrobble,
+            503,
+            "<html>Service Unavailable</html>".to_string(),
+        ));
+        let report = queue.flush(&lastfm_with(outage)).await.unwrap();
+        assert!(matches!(
+            report.stopped,
+            Some(FlushStop::Unavailable(Error::HttpStatus(503)))
+        ));
+        assert_eq!(report.remaining, 2);
+
+        let rejected = Arc::new(MockTransport::new().respond(
+            LastfmMethod::TrackScrobble,
+            json!({ "error": 9, "message": "Invalid session key - Please re-authenticate" }),
+        ));
+        let report = queue.flush(&lastfm_with(rejected)).await.unwrap();
+        assert!(matches!(report.stopped, Some(FlushStop::Rejected(_))));
+        assert_eq!(report.remaining, 2);
+
+        let accepted = Arc::new(
+            MockTransport::new()
+                .respond(LastfmMethod::TrackScrobble, scrobble_echo(queue.entries())),
+        );
+        let report = queue.flush(&lastfm_with(accepted)).await.unwrap();
+        assert!(report.stopped.is_none());
+        assert_eq!(report.submitted.len(), 2);
+        assert!(ScrobbleQueue::open(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_push_deduplicates_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.jsonl");
+        let now = unix_now();
+
+        let mut queue = ScrobbleQueue::open(&path).unwrap();
+        assert!(queue.push(ScrobbleEntry::new("A", "T", now)).unwrap());
+        assert!(!queue.push(ScrobbleEntry::new("A", "T", now)).unwrap());
+        assert!(queue.push(ScrobbleEntry::new("A", "T", now + 1)).unwrap());
+
+        let reopened = ScrobbleQueue::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.entries(), queue.entries());
+    }
+
+    #[test]
+    fn test_push_rejects_invalid_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.jsonl");
+
+        let mut queue = ScrobbleQueue::open(&path).unwrap();
+        assert!(matches!(
+            queue.push(ScrobbleEntry::new("A", " ", unix_now())),
+            Err(Error::Validation(ValidationError::Missing("track")))
+        ));
+        assert!(queue.is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_prune_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.jsonl");
+        let now = unix_now();
+        let too_old = now - SCROBBLE_MAX_AGE.as_secs() - 60;
+
+        let mut queue = ScrobbleQueue::open(&path).unwrap();
+        queue.push(ScrobbleEntry::new("A", "Old", too_old)).unwrap();
+        queue.push(ScrobbleEntry::new("A", "New", now)).unwrap();
+
+        let expired = queue.prune_expired().unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].track, "Old");
+        assert_eq!(ScrobbleQueue::open(&path).unwrap().len(), 1);
+    }
+}
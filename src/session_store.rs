@@ -0,0 +1,268 @@
+//! Persistence for session keys obtained through the auth methods.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::{Error, Result, Session};
+
+/// A place to keep sessions between runs, keyed by API key and username.
+pub trait SessionStore: Send + Sync {
+    /// Loads the session saved for `username`, if any.
+    fn load(&self, api_key: &str, username: &str) -> Result<Option<Session>>;
+
+    /// Saves `session` under its username, replacing any previous one.
+    fn save(&self, api_key: &str, session: &Session) -> Result<()>;
+
+    /// Removes the session saved for `username`, if any.
+    fn clear(&self, api_key: &str, username: &str) -> Result<()>;
+}
+
+type Sessions = HashMap<String, HashMap<String, Session>>;
+
+/// A [`SessionStore`] that only keeps sessions for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<Sessions>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sessions(&self) -> Result<std::sync::MutexGuard<'_, Sessions>> {
+        self.sessions
+            .lock()
+            .map_err(|_| Error::Generic("Session store lock was poisoned.".to_string()))
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn load(&self, api_key: &str, username: &str) -> Result<Option<Session>> {
+        Ok(self
+            .sessions()?
+            .get(api_key)
+            .and_then(|sessions| sessions.get(username))
+            .cloned())
+    }
+
+    fn save(&self, api_key: &str, session: &Session) -> Result<()> {
+        self.sessions()?
+            .entry(api_key.to_string())
+            .or_default()
+            .insert(session.name.clone(), session.clone());
+        Ok(())
+    }
+
+    fn clear(&self, api_key: &str, username: &str) -> Result<()> {
+        if let Some(sessions) = self.sessions()?.get_mut(api_key) {
+            sessions.remove(username);
+        }
+        Ok(())
+    }
+}
+
+/// A [`SessionStore`] backed by a JSON file.
+///
+/// On Unix the file is created with `0600` permissions, since session keys grant full
+/// access to the user's account.
+#[derive(Debug)]
+pub struct FileSessionStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileSessionStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileSessionStore {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<Sessions> {
+        if !self.path.exists() {
+            return Ok(Sessions::new());
+        }
+        let contents = fs::read_to_string(&self.path)?;
+        if contents.trim().is_empty() {
+            return Ok(Sessions::new());
+        }
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn write(&self, sessions: &Sessions) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        // The mode only applies to new files, so a temporary file left behind by a crash is
+        // removed rather than reused with whatever permissions it had.
+        let tmp_path = tmp_path(&self.path);
+        match fs::remove_file(&tmp_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(serde_json::to_string_pretty(sessions)?.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn update<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Sessions),
+    {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| Error::Generic("Session store lock was poisoned.".to_string()))?;
+
+        let mut sessions = self.read()?;
+        f(&mut sessions);
+        self.write(&sessions)
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn load(&self, api_key: &str, username: &str) -> Result<Option<Session>> {
+        Ok(self
+            .read()?
+            .get(api_key)
+            .and_then(|sessions| sessions.get(username))
+            .cloned())
+    }
+
+    fn save(&self, api_key: &str, session: &Session) -> Result<()> {
+        self.update(|sessions| {
+            sessions
+                .entry(api_key.to_string())
+                .or_default()
+                .insert(session.name.clone(), session.clone());
+        })
+    }
+
+    fn clear(&self, api_key: &str, username: &str) -> Result<()> {
+        self.update(|sessions| {
+            if let Some(user_sessions) = sessions.get_mut(api_key) {
+                user_sessions.remove(username);
+            }
+            sessions.retain(|_, user_sessions| !user_sessions.is_empty());
+        })
+    }
+}
+
+/// The file a new version of `path` is written to before it replaces `path`, e.g.
+/// `sessions.json.tmp`. Appending keeps it distinct from files that only share the stem.
+pub(crate) fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session {
+            name: "uppercase_".to_string(),
+            key: "session_key".to_string(),
+            subscriber: false,
+        }
+    }
+
+    fn assert_round_trip(store: &dyn SessionStore) {
+        assert_eq!(store.load("api_key", "uppercase_").unwrap(), None);
+
+        store.save("api_key", &session()).unwrap();
+        assert_eq!(
+            store.load("api_key", "uppercase_").unwrap(),
+            Some(session())
+        );
+        assert_eq!(store.load("other_api_key", "uppercase_").unwrap(), None);
+
+        store.clear("api_key", "uppercase_").unwrap();
+        assert_eq!(store.load("api_key", "uppercase_").unwrap(), None);
+    }
+
+    #[test]
+    fn test_memory_session_store() {
+        assert_round_trip(&MemorySessionStore::new());
+    }
+
+    #[test]
+    fn test_file_session_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        assert_round_trip(&FileSessionStore::new(&path));
+
+        FileSessionStore::new(&path)
+            .save("api_key", &session())
+            .unwrap();
+        let reopened = FileSessionStore::new(&path);
+        assert_eq!(
+            reopened.load("api_key", "uppercase_").unwrap(),
+            Some(session())
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stale_tmp_file_does_not_leak_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let tmp_path = dir.path().join("sessions.json.tmp");
+        fs::write(&tmp_path, "{}").unwrap();
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        FileSessionStore::new(&path)
+            .save("api_key", &session())
+            .unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(!tmp_path.exists());
+    }
+
+    #[test]
+    fn test_tmp_path_keeps_the_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let unrelated = dir.path().join("sessions.tmp");
+        fs::write(&unrelated, "keep").unwrap();
+
+        FileSessionStore::new(dir.path().join("sessions.json"))
+            .save("api_key", &session())
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&unrelated).unwrap(), "keep");
+    }
+}
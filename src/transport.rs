@@ -0,0 +1,305 @@
+//! The HTTP layer used by [`Lastfm`](crate::Lastfm) to reach the API.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use reqwest::{Client as ReqwestClient, Method};
+use serde_json::{json, Value};
+
+use crate::api::LastfmMethod;
+use crate::{Error, Result};
+
+/// The future returned by [`Transport::send`].
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send + 'a>>;
+
+/// The raw HTTP response to an API call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends API calls over the wire.
+///
+/// The default implementation is [`ReqwestTransport`]. Tests can use [`MockTransport`]
+/// to serve canned responses without network access or credentials.
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends `params` to `url`, as a query string for `GET` or a form body for `POST`.
+    fn send<'a>(
+        &'a self,
+        url: &'a str,
+        http_method: Method,
+        params: &'a HashMap<String, String>,
+    ) -> TransportFuture<'a>;
+}
+
+/// A [`Transport`] backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: ReqwestClient,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: ReqwestClient) -> Self {
+        ReqwestTransport { client }
+    }
+
+    pub fn client(&self) -> &ReqwestClient {
+        &self.client
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send<'a>(
+        &'a self,
+        url: &'a str,
+        http_method: Method,
+        params: &'a HashMap<String, String>,
+    ) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let response = match http_method {
+                Method::GET => self.client.get(url).query(params).send().await?,
+                Method::POST => self.client.post(url).form(params).send().await?,
+                _ => return Err(Error::Generic("Unsupported HTTP method".to_string())),
+            };
+
+            Ok(TransportResponse {
+                status: response.status().as_u16(),
+                body: response.text().await?,
+            })
+        })
+    }
+}
+
+/// A [`Transport`] that serves canned responses per Last.fm method and records every request.
+///
+/// Responses queued with [`MockTransport::respond_once`] are served first, in order. Methods
+/// without a canned response are answered with Last.fm's "Invalid Method" error.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<String, TransportResponse>>,
+    queued: Mutex<HashMap<String, VecDeque<TransportResponse>>>,
+    requests: Mutex<Vec<HashMap<String, String>>>,
+    http_methods: Mutex<Vec<Method>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers every call to `method` with `body` and a `200 OK` status.
+    pub fn respond(self, method: LastfmMethod, body: Value) -> Self {
+        self.respond_with_status(method, 200, body.to_string())
+    }
+
+    /// Answers every call to `method` with the given status and raw body.
+    pub fn respond_with_status(self, method: LastfmMethod, status: u16, body: String) -> Self {
+        self.responses
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(method.to_string(), TransportResponse { status, body });
+        self
+    }
+
+    /// Answers a single call to `method` with `body` and a `200 OK` status. Responses queued
+    /// for the same method are served in the order they were added.
+    pub fn respond_once(self, method: LastfmMethod, body: Value) -> Self {
+        self.respond_once_with_status(method, 200, body.to_string())
+    }
+
+    /// Like [`respond_once`](MockTransport::respond_once), with the given status and raw body.
+    pub fn respond_once_with_status(self, method: LastfmMethod, status: u16, body: String) -> Self {
+        self.queued
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .entry(method.to_string())
+            .or_default()
+            .push_back(TransportResponse { status, body });
+        self
+    }
+
+    /// The parameters of every request sent so far, in order.
+    pub fn requests(&self) -> Vec<HashMap<String, String>> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// The HTTP method of every request sent so far, in order.
+    pub fn http_methods(&self) -> Vec<Method> {
+        self.http_methods
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// The parameters of the most recent request, if any.
+    pub fn last_request(&self) -> Option<HashMap<String, String>> {
+        self.requests().pop()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send<'a>(
+        &'a self,
+        _url: &'a str,
+        http_method: Method,
+        params: &'a HashMap<String, String>,
+    ) -> TransportFuture<'a> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(params.clone());
+        self.http_methods
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(http_method);
+
+        let method = params.get("method").cloned().unwrap_or_default();
+        let queued = self
+            .queued
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_mut(&method)
+            .and_then(VecDeque::pop_front);
+        let response = queued
+            .or_else(|| {
+                self.responses
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .get(&method)
+                    .cloned()
+            })
+            .unwrap_or_else(|| TransportResponse {
+                status: 400,
+                body: json!({
+                    "error": 3,
+                    "message": "Invalid Method - No method with that name in this package",
+                })
+                .to_string(),
+            });
+
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// Fixtures shared by the unit tests that run against a [`MockTransport`].
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use super::Transport;
+    use crate::{Lastfm, ScrobbleEntry};
+
+    /// A client with test credentials and a session key, sending every request to `transport`.
+    pub(crate) fn lastfm_with(transport: Arc<dyn Transport>) -> Lastfm {
+        Lastfm::builder()
+            .api_key("test_api_key".to_string())
+            .api_secret("test_api_secret".to_string())
+            .session_key("test_session_key".to_string())
+            .transport(transport)
+            .build()
+            .unwrap()
+    }
+
+    /// The `track.scrobble` response accepting every one of `entries`.
+    pub(crate) fn scrobble_echo(entries: &[ScrobbleEntry]) -> Value {
+        let scrobbles: Vec<Value> = entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "artist": { "corrected": "0", "#text": entry.artist },
+                    "track": { "corrected": "0", "#text": entry.track },
+                    "album": { "corrected": "0" },
+                    "albumArtist": { "corrected": "0" },
+                    "timestamp": entry.timestamp.to_string(),
+                    "ignoredMessage": { "code": "0", "#text": "" }
+                })
+            })
+            .collect();
+
+        json!({
+            "scrobbles": {
+                "scrobble": scrobbles,
+                "@attr": { "accepted": entries.len(), "ignored": 0 }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::lastfm_with;
+    use super::*;
+    use crate::APIResponse;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_mock_serves_canned_response() {
+        let transport = Arc::new(MockTransport::new().respond(
+            LastfmMethod::TrackScrobble,
+            json!({
+                "scrobbles": {
+                    "scrobble": {
+                        "artist": { "corrected": "0", "#text": "Cher" },
+                        "track": { "corrected": "0", "#text": "Believe" },
+                        "album": { "corrected": "0" },
+                        "albumArtist": { "corrected": "0" },
+                        "timestamp": "1700000000",
+                        "ignoredMessage": { "code": "0", "#text": "" }
+                    },
+                    "@attr": { "accepted": 1, "ignored": 0 }
+                }
+            }),
+        ));
+        let lastfm = lastfm_with(transport.clone());
+
+        let response = lastfm
+            .track()
+            .scrobble()
+            .artist(Some("Cher"))
+            .track(Some("Believe"))
+            .timestamp(Some(1_700_000_000))
+            .send()
+            .await
+            .unwrap();
+
+        match response {
+            APIResponse::Success(response) => assert_eq!(response.accepted(), 1),
+            APIResponse::Error(err) => panic!("unexpected API error: {}", err.message),
+        }
+
+        let request = transport.last_request().unwrap();
+        assert_eq!(request["method"], "track.scrobble");
+        assert_eq!(request["sk"], "test_session_key");
+        assert!(request.contains_key("api_sig"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_rejects_unknown_method() {
+        let transport = Arc::new(MockTransport::new());
+        let lastfm = lastfm_with(transport.clone());
+
+        let response = lastfm
+            .album()
+            .get_info()
+            .artist("Cher")
+            .album("Believe")
+            .send()
+            .await
+            .unwrap();
+
+        match response {
+            APIResponse::Error(err) => assert_eq!(err.error, 3),
+            APIResponse::Success(_) => panic!("expected an API error"),
+        }
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -4,6 +4,6 @@ mod tests {
 
     #[test]
     fn test_lastfm_api_url() {
-        assert_eq!(LASTFM_API_URL, "http://ws.audioscrobbler.com/2.0/");
+        assert_eq!(LASTFM_API_URL, "https://ws.audioscrobbler.com/2.0/");
     }
 }